@@ -2,6 +2,7 @@ use openai_api_rs::v1::api::OpenAIClient;
 use openai_api_rs::v1::assistant::AssistantRequest;
 use openai_api_rs::v1::common::GPT4_O;
 use openai_api_rs::v1::message::{CreateMessageRequest, MessageRole};
+use openai_api_rs::v1::pagination::ListParams;
 use openai_api_rs::v1::run::CreateRunRequest;
 use openai_api_rs::v1::thread::CreateThreadRequest;
 use std::collections::HashMap;
@@ -66,7 +67,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     let list_message_result = client
-        .list_messages(thread_result.inner.id.clone())
+        .list_messages(thread_result.inner.id.clone(), ListParams::new())
         .await
         .unwrap();
     for data in list_message_result.inner.data {
@@ -0,0 +1,36 @@
+use openai_api_rs::v1::api::OpenAIClient;
+use openai_api_rs::v1::azure::AzureConfig;
+use openai_api_rs::v1::chat_completion::{self, chat_completion::ChatCompletionRequest};
+use openai_api_rs::v1::common::GPT4_O;
+use std::env;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let api_key = env::var("AZURE_OPENAI_API_KEY").unwrap().to_string();
+    let resource = env::var("AZURE_OPENAI_RESOURCE").unwrap().to_string();
+    let deployment = env::var("AZURE_OPENAI_DEPLOYMENT").unwrap().to_string();
+
+    let azure = AzureConfig::from_resource(&resource, "2024-10-21")
+        .with_deployment(GPT4_O, deployment)
+        .with_api_key(api_key);
+    let client = OpenAIClient::builder().with_azure(azure).build()?;
+
+    let req = ChatCompletionRequest::new(
+        GPT4_O.to_string(),
+        vec![chat_completion::ChatCompletionMessage {
+            role: chat_completion::MessageRole::user,
+            content: chat_completion::Content::Text(String::from("What is bitcoin?")),
+            name: None,
+            tool_calls: None,
+            tool_call_id: None,
+            audio: None,
+        }],
+    );
+
+    let result = client.chat_completion(req).await?;
+    println!("Content: {:?}", result.inner.choices[0].message.content);
+
+    Ok(())
+}
+
+// AZURE_OPENAI_API_KEY=xxxx AZURE_OPENAI_RESOURCE=my-resource AZURE_OPENAI_DEPLOYMENT=gpt-4o cargo run --package openai-api-rs --example azure
@@ -17,6 +17,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             name: None,
             tool_calls: None,
             tool_call_id: None,
+            audio: None,
         }],
     );
 
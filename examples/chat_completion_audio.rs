@@ -0,0 +1,56 @@
+use openai_api_rs::v1::api::OpenAIClient;
+use openai_api_rs::v1::chat_completion::chat_completion::ChatCompletionRequest;
+use openai_api_rs::v1::chat_completion::{
+    AudioOutputConfig, AudioOutputFormat, ChatCompletionMessage, Content, MessageRole, Modality,
+};
+use openai_api_rs::v1::common::GPT_AUDIO;
+use std::env;
+
+fn user(text: &str) -> ChatCompletionMessage {
+    ChatCompletionMessage {
+        role: MessageRole::user,
+        content: Content::Text(text.to_string()),
+        name: None,
+        tool_calls: None,
+        tool_call_id: None,
+        audio: None,
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let api_key = env::var("OPENAI_API_KEY").unwrap().to_string();
+    let client = OpenAIClient::builder().with_api_key(api_key).build()?;
+
+    let mut messages = vec![user("Is a golden retriever a good family dog?")];
+    let req = ChatCompletionRequest::new(GPT_AUDIO.to_string(), messages.clone())
+        .modalities(vec![Modality::Text, Modality::Audio])
+        .audio(AudioOutputConfig::new("alloy", AudioOutputFormat::Wav));
+
+    let result = client.chat_completion(req.clone()).await?;
+    let audio = result.inner.choices[0].message.audio.clone().unwrap();
+    println!("Transcript: {}", audio.transcript);
+    std::fs::write("answer.wav", &audio.data)?;
+
+    // Refer to the spoken answer instead of sending it back.
+    messages.push(ChatCompletionMessage {
+        role: MessageRole::assistant,
+        content: Content::Text(String::new()),
+        name: None,
+        tool_calls: None,
+        tool_call_id: None,
+        audio: Some(audio.reference()),
+    });
+    messages.push(user("Why do you say that?"));
+
+    let mut req = req;
+    req.messages = messages;
+    let result = client.chat_completion(req).await?;
+    let audio = result.inner.choices[0].message.audio.as_ref().unwrap();
+    println!("Transcript: {}", audio.transcript);
+    std::fs::write("follow_up.wav", &audio.data)?;
+
+    Ok(())
+}
+
+// OPENAI_API_KEY=xxxx cargo run --package openai-api-rs --example chat_completion_audio
@@ -20,12 +20,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             name: None,
             tool_calls: None,
             tool_call_id: None,
+            audio: None,
         }],
     );
 
     let mut result = client.chat_completion_stream(req).await?;
     while let Some(response) = result.next().await {
-        match response.clone() {
+        match response? {
             ChatCompletionStreamResponse::ToolCall(toolcalls) => {
                 println!("Tool Call: {:?}", toolcalls);
             }
@@ -35,12 +36,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             ChatCompletionStreamResponse::Content(content) => {
                 println!("Content: {:?}", content);
             }
+            ChatCompletionStreamResponse::Audio(audio) => {
+                println!("Audio: {:?}", audio.transcript);
+            }
             ChatCompletionStreamResponse::Done => {
                 println!("Done");
             }
         }
     }
 
+    if !result.is_done() {
+        println!("Stream ended before [DONE]");
+    }
+
     Ok(())
 }
 
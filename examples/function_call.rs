@@ -43,6 +43,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             name: None,
             tool_calls: None,
             tool_call_id: None,
+            audio: None,
         }],
     )
     .tools(vec![Tool {
@@ -54,7 +55,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 schema_type: types::JSONSchemaType::Object,
                 properties: Some(properties),
                 required: Some(vec![String::from("coin")]),
+                ..Default::default()
             },
+            strict: None,
         },
     }])
     .tool_choice(ToolChoiceType::Auto);
@@ -98,6 +101,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Some(FinishReason::content_filter) => {
             println!("ContentFilter");
         }
+        Some(FinishReason::function_call) => {
+            println!("FunctionCall");
+        }
         Some(FinishReason::null) => {
             println!("Null");
         }
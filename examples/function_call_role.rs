@@ -39,6 +39,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             name: None,
             tool_calls: None,
             tool_call_id: None,
+            audio: None,
         }],
     )
     .tools(vec![chat_completion::Tool {
@@ -50,7 +51,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 schema_type: types::JSONSchemaType::Object,
                 properties: Some(properties),
                 required: Some(vec![String::from("coin")]),
+                ..Default::default()
             },
+            strict: None,
         },
     }]);
 
@@ -95,6 +98,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             name: None,
                             tool_calls: None,
                             tool_call_id: None,
+                            audio: None,
                         },
                         chat_completion::ChatCompletionMessage {
                             role: chat_completion::MessageRole::function,
@@ -105,6 +109,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             name: Some(String::from("get_coin_price")),
                             tool_calls: None,
                             tool_call_id: None,
+                            audio: None,
                         },
                     ],
                 );
@@ -116,6 +121,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Some(chat_completion::FinishReason::content_filter) => {
             println!("ContentFilter");
         }
+        Some(chat_completion::FinishReason::function_call) => {
+            println!("FunctionCall");
+        }
         Some(chat_completion::FinishReason::null) => {
             println!("Null");
         }
@@ -11,9 +11,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let mut req = CreateResponseRequest::new();
     req.model = Some(GPT4_1_MINI.to_string());
-    req.input = Some(json!(
-        "Tell me a three sentence bedtime story about a unicorn."
-    ));
+    req.input = Some("Tell me a three sentence bedtime story about a unicorn.".into());
     req.extra.insert("temperature".to_string(), json!(0.7));
 
     let resp = client.create_response(req).await?;
@@ -21,6 +19,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         "response id: {} status: {:?}",
         resp.inner.id, resp.inner.status
     );
-    println!("response output: {:?}", resp.inner.output);
+    println!("response output: {}", resp.inner.output_text());
     Ok(())
 }
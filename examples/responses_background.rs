@@ -0,0 +1,48 @@
+use futures_util::StreamExt;
+use openai_api_rs::v1::api::OpenAIClient;
+use openai_api_rs::v1::common::GPT4_1_MINI;
+use openai_api_rs::v1::responses::responses::{CreateResponseRequest, PollOptions};
+use openai_api_rs::v1::responses::responses_stream::{ResponseStreamEvent, ResponseStreamResponse};
+use std::env;
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let api_key = env::var("OPENAI_API_KEY").unwrap();
+    let client = OpenAIClient::builder().with_api_key(api_key).build()?;
+
+    let mut req = CreateResponseRequest::new();
+    req.model = Some(GPT4_1_MINI.to_string());
+    req.input = Some("Write a short report on the history of the bicycle.".into());
+
+    let created = client.create_background_response(req).await?;
+    let response_id = created.inner.id;
+    println!("started {response_id}");
+
+    // Read a few events, then drop the connection as if the client restarted.
+    let mut stream = client.resume_response_stream(&response_id, None).await?;
+    for _ in 0..10 {
+        if stream.next().await.is_none() {
+            break;
+        }
+    }
+    let last_seen = stream.last_sequence_number();
+    drop(stream);
+
+    // Pick up where we left off.
+    let mut stream = client
+        .resume_response_stream(&response_id, last_seen)
+        .await?;
+    while let Some(event) = stream.next().await {
+        if let ResponseStreamResponse::Event(ResponseStreamEvent::OutputTextDelta(delta)) = event? {
+            print!("{}", delta.delta);
+        }
+    }
+
+    let poll = PollOptions::new().with_timeout(Duration::from_secs(600));
+    let response = client.wait_for_response(&response_id, poll).await?;
+    println!("\n\nstatus: {:?}", response.status);
+    Ok(())
+}
+
+// OPENAI_API_KEY=xxxx cargo run --package openai-api-rs --example responses_background
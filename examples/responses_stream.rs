@@ -2,9 +2,8 @@ use futures_util::StreamExt;
 use openai_api_rs::v1::api::OpenAIClient;
 use openai_api_rs::v1::common::GPT4_1_MINI;
 use openai_api_rs::v1::responses::responses_stream::{
-    CreateResponseStreamRequest, ResponseStreamResponse,
+    CreateResponseStreamRequest, ResponseStreamEvent, ResponseStreamResponse,
 };
-use serde_json::{json, Value};
 use std::env;
 use std::io::{self, Write};
 
@@ -15,28 +14,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let mut req = CreateResponseStreamRequest::new();
     req.model = Some(GPT4_1_MINI.to_string());
-    req.input = Some(json!("What is bitcoin? Please answer in detail."));
+    req.input = Some("What is bitcoin? Please answer in detail.".into());
 
     let mut stream = client.create_response_stream(req).await?;
     let mut full_text = String::new();
 
     while let Some(event) = stream.next().await {
-        match event {
-            ResponseStreamResponse::Event(evt) => {
-                if let Some("response.output_text.delta") = evt.event.as_deref() {
-                    if let Some(delta) = evt.data.get("delta").and_then(Value::as_str) {
-                        print!("{delta}");
-                        io::stdout().flush()?;
-                        full_text.push_str(delta);
-                        continue;
-                    }
-                }
-
-                if let Some(name) = evt.event.as_deref() {
-                    println!("\nEvent: {name} => {}", evt.data);
-                } else {
-                    println!("Event data: {}", evt.data);
-                }
+        match event? {
+            ResponseStreamResponse::Event(ResponseStreamEvent::OutputTextDelta(delta)) => {
+                print!("{}", delta.delta);
+                io::stdout().flush()?;
+                full_text.push_str(&delta.delta);
+            }
+            ResponseStreamResponse::Event(ResponseStreamEvent::Completed(done)) => {
+                println!("\n\nUsage: {:?}", done.response.usage);
+            }
+            ResponseStreamResponse::Event(event) => {
+                println!("\nEvent #{:?}", event.sequence_number());
             }
             ResponseStreamResponse::Done => {
                 println!("\n\nDone streaming response.");
@@ -0,0 +1,78 @@
+use openai_api_rs::v1::api::OpenAIClient;
+use openai_api_rs::v1::chat_completion::{
+    chat_completion::ChatCompletionRequest, ChatCompletionMessage, Content, MessageRole,
+};
+use openai_api_rs::v1::common::GPT4_O;
+use openai_api_rs::v1::tool_registry::ToolRegistry;
+use openai_api_rs::v1::types;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+
+#[derive(Deserialize)]
+struct Currency {
+    coin: String,
+}
+
+async fn get_coin_price(args: Currency) -> Result<f64, String> {
+    match args.coin.to_lowercase().as_str() {
+        "btc" | "bitcoin" => Ok(10000.0),
+        "eth" | "ethereum" => Ok(1000.0),
+        coin => Err(format!("unknown coin {coin}")),
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let api_key = env::var("OPENAI_API_KEY").unwrap().to_string();
+    let client = OpenAIClient::builder().with_api_key(api_key).build()?;
+
+    let mut properties = HashMap::new();
+    properties.insert(
+        "coin".to_string(),
+        Box::new(types::JSONSchemaDefine {
+            schema_type: Some(types::JSONSchemaType::String),
+            description: Some("The cryptocurrency to get the price of".to_string()),
+            ..Default::default()
+        }),
+    );
+
+    let mut registry = ToolRegistry::new();
+    registry.register(
+        types::Function {
+            name: String::from("get_coin_price"),
+            description: Some(String::from("Get the price of a cryptocurrency")),
+            parameters: types::FunctionParameters {
+                schema_type: types::JSONSchemaType::Object,
+                properties: Some(properties),
+                required: Some(vec![String::from("coin")]),
+                ..Default::default()
+            },
+            strict: None,
+        },
+        get_coin_price,
+    );
+
+    let req = ChatCompletionRequest::new(
+        GPT4_O.to_string(),
+        vec![ChatCompletionMessage {
+            role: MessageRole::user,
+            content: Content::Text(String::from(
+                "Which is more expensive, Bitcoin or Ethereum?",
+            )),
+            name: None,
+            tool_calls: None,
+            tool_call_id: None,
+            audio: None,
+        }],
+    );
+
+    let run = client.run_tools(req, &registry).await?;
+    for message in &run.messages {
+        println!("{:?}: {:?}", message.role, message.content);
+    }
+    println!("finished after {} requests", run.iterations);
+    Ok(())
+}
+
+// OPENAI_API_KEY=xxxx cargo run --package openai-api-rs --example tool_registry
@@ -1,6 +1,6 @@
 use openai_api_rs::v1::api::OpenAIClient;
 use openai_api_rs::v1::chat_completion::chat_completion::ChatCompletionRequest;
-use openai_api_rs::v1::chat_completion::{self};
+use openai_api_rs::v1::chat_completion::{self, ContentPart, ImageDetail};
 use openai_api_rs::v1::common::GPT4_O;
 use std::env;
 
@@ -13,25 +13,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         GPT4_O.to_string(),
         vec![chat_completion::ChatCompletionMessage {
             role: chat_completion::MessageRole::user,
-            content: chat_completion::Content::ImageUrl(vec![
-                chat_completion::ImageUrl {
-                    r#type: chat_completion::ContentType::text,
-                    text: Some(String::from("What's in this image?")),
-                    image_url: None,
-                },
-                chat_completion::ImageUrl {
-                    r#type: chat_completion::ContentType::image_url,
-                    text: None,
-                    image_url: Some(chat_completion::ImageUrlType {
-                        url: String::from(
-                            "https://upload.wikimedia.org/wikipedia/commons/5/50/Bitcoin.png",
-                        ),
-                    }),
-                },
+            content: chat_completion::Content::Parts(vec![
+                ContentPart::text("What's in this image?"),
+                ContentPart::image_url(
+                    "https://upload.wikimedia.org/wikipedia/commons/5/50/Bitcoin.png",
+                )
+                .with_detail(ImageDetail::Low),
             ]),
             name: None,
             tool_calls: None,
             tool_call_id: None,
+            audio: None,
         }],
     );
 
@@ -1,5 +1,7 @@
+use crate::v1::credentials::CredentialProvider;
 use futures_util::stream::{SplitSink, SplitStream};
 use futures_util::StreamExt;
+use std::sync::Arc;
 use tokio::net::TcpStream;
 use tokio_tungstenite::{
     connect_async,
@@ -13,6 +15,8 @@ pub struct RealtimeClient {
     pub wss_url: String,
     pub api_key: String,
     pub model: String,
+    /// When set, consulted on every `connect` instead of `api_key`.
+    pub credentials: Option<Arc<dyn CredentialProvider>>,
 }
 
 impl RealtimeClient {
@@ -26,9 +30,15 @@ impl RealtimeClient {
             wss_url,
             api_key,
             model,
+            credentials: None,
         }
     }
 
+    pub fn with_credential_provider(mut self, provider: impl CredentialProvider + 'static) -> Self {
+        self.credentials = Some(Arc::new(provider));
+        self
+    }
+
     pub async fn connect(
         &self,
     ) -> Result<
@@ -40,7 +50,10 @@ impl RealtimeClient {
     > {
         let url = format!("{}?model={}", self.wss_url, self.model);
         let mut request = url.into_client_request()?;
-        let api_key = self.api_key.clone();
+        let api_key = match &self.credentials {
+            Some(provider) => provider.credential().await?,
+            None => self.api_key.clone(),
+        };
         request
             .headers_mut()
             .insert("Authorization", format!("Bearer {api_key}").parse()?);
@@ -6,18 +6,29 @@ use crate::v1::audio::{
     AudioSpeechRequest, AudioSpeechResponse, AudioTranscriptionRequest, AudioTranscriptionResponse,
     AudioTranslationRequest, AudioTranslationResponse,
 };
+use crate::v1::azure::{AzureAuth, AzureConfig};
 use crate::v1::batch::{BatchResponse, CreateBatchRequest, ListBatchResponse};
 use crate::v1::chat_completion::chat_completion::{ChatCompletionRequest, ChatCompletionResponse};
+use crate::v1::chat_completion::chat_completion_store::{
+    ChatCompletionList, ChatCompletionListFilter, ChatCompletionMessageList,
+    StoredChatCompletionMessage, UpdateChatCompletionRequest,
+};
 use crate::v1::chat_completion::chat_completion_stream::{
-    ChatCompletionStream, ChatCompletionStreamRequest, ChatCompletionStreamResponse,
+    ChatCompletionChunkStream, ChatCompletionStream, ChatCompletionStreamRequest,
 };
+use crate::v1::chat_completion::{ChatCompletionMessage, Content, MessageRole, ResponseFormat};
 use crate::v1::common;
 use crate::v1::completion::{CompletionRequest, CompletionResponse};
+use crate::v1::conversation::{
+    ConversationItem, ConversationItemList, ConversationObject, CreateConversationItemsRequest,
+    CreateConversationRequest, UpdateConversationRequest,
+};
+use crate::v1::credentials::{CredentialProvider, StaticCredential};
 use crate::v1::edit::{EditRequest, EditResponse};
 use crate::v1::embedding::{EmbeddingRequest, EmbeddingResponse};
-use crate::v1::error::APIError;
+use crate::v1::error::{APIError, ApiErrorResponse, ErrorObject};
 use crate::v1::file::{
-    FileDeleteRequest, FileDeleteResponse, FileListResponse, FileRetrieveResponse,
+    FileData, FileDeleteRequest, FileDeleteResponse, FileListResponse, FileRetrieveResponse,
     FileUploadRequest, FileUploadResponse,
 };
 use crate::v1::fine_tuning::{
@@ -33,26 +44,32 @@ use crate::v1::message::{
     CreateMessageRequest, ListMessage, ListMessageFile, MessageFileObject, MessageObject,
     ModifyMessageRequest,
 };
+use crate::v1::middleware::{Middleware, RequestInfo};
 use crate::v1::model::{ModelResponse, ModelsResponse};
 use crate::v1::moderation::{CreateModerationRequest, CreateModerationResponse};
+use crate::v1::pagination::{CursorPage, ListParams, PageItem};
+use crate::v1::rate_limiter::{estimate_tokens, RateLimiter};
+use crate::v1::request_options::RequestOptions;
 use crate::v1::responses::responses::{
-    CallResponse, CountTokensRequest, CountTokensResponse, CreateResponseRequest, ListResponses,
-    ResponseObject,
-};
-use crate::v1::responses::responses_stream::{
-    CreateResponseStreamRequest, ResponseStream, ResponseStreamResponse,
+    CallResponse, CountTokensRequest, CountTokensResponse, CreateResponseRequest, InputItem,
+    PollOptions, ResponseInput, ResponseInputItemList, ResponseObject, TextFormat,
 };
+use crate::v1::responses::responses_stream::{CreateResponseStreamRequest, ResponseStream};
+use crate::v1::retry::RetryPolicy;
 use crate::v1::run::{
     CreateRunRequest, CreateThreadAndRunRequest, ListRun, ListRunStep, ModifyRunRequest, RunObject,
     RunStepObject,
 };
+use crate::v1::schema::{Parsed, StrictSchema};
 use crate::v1::thread::{CreateThreadRequest, ModifyThreadRequest, ThreadObject};
+use crate::v1::tool_registry::{ResponseToolRun, ToolRegistry, ToolRun};
 
 use bytes::Bytes;
-use futures_util::Stream;
+use futures_util::{stream, Stream, StreamExt, TryStreamExt};
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use reqwest::multipart::{Form, Part};
-use reqwest::{Client, Method, Response};
+use reqwest::{Client, Method, Response, StatusCode};
+use serde::de::DeserializeOwned;
 use serde::Serialize;
 use serde_json::{to_value, Value};
 use url::Url;
@@ -62,27 +79,84 @@ use std::fs::{create_dir_all, File};
 use std::io::Read;
 use std::io::Write;
 use std::path::Path;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 const API_URL_V1: &str = "https://api.openai.com/v1";
+const ENCRYPTED_REASONING: &str = "reasoning.encrypted_content";
 
 #[derive(Default)]
 pub struct OpenAIClientBuilder {
     api_endpoint: Option<String>,
-    api_key: Option<String>,
+    credentials: Option<Arc<dyn CredentialProvider>>,
     organization: Option<String>,
     proxy: Option<String>,
     timeout: Option<u64>,
+    connect_timeout: Option<Duration>,
     headers: Option<HeaderMap>,
+    http_client: Option<Client>,
+    retry_policy: Option<RetryPolicy>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    middleware: Vec<Arc<dyn Middleware>>,
+    azure: Option<AzureConfig>,
+    options: RequestOptions,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct OpenAIClient {
     api_endpoint: String,
-    api_key: Option<String>,
+    credentials: Option<Arc<dyn CredentialProvider>>,
     organization: Option<String>,
-    proxy: Option<String>,
-    timeout: Option<u64>,
     headers: Option<HeaderMap>,
+    http_client: Client,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    middleware: Vec<Arc<dyn Middleware>>,
+    azure: Option<AzureConfig>,
+    options: RequestOptions,
+    idempotency_key: Option<IdempotencyKey>,
+}
+
+/// Idempotency key of a client made by `with_options`, used up by the first
+/// call that writes. Each clone keeps its own copy of whether it was used.
+#[derive(Debug)]
+struct IdempotencyKey {
+    key: String,
+    used: AtomicBool,
+}
+
+impl Clone for IdempotencyKey {
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key.clone(),
+            used: AtomicBool::new(self.used.load(Ordering::SeqCst)),
+        }
+    }
+}
+
+/// Body of a streaming response, as consumed by the SSE stream types.
+pub type ByteStream = Pin<Box<dyn Stream<Item = Result<Bytes, APIError>> + Send>>;
+
+enum RequestBody<'a> {
+    Empty,
+    Json(Value),
+    /// Multipart forms cannot be cloned, so they are rebuilt for every attempt.
+    Multipart {
+        model: Option<&'a str>,
+        build_form: &'a (dyn Fn() -> Result<Form, APIError> + Sync),
+    },
+}
+
+impl RequestBody<'_> {
+    fn model(&self) -> Option<&str> {
+        match self {
+            RequestBody::Empty => None,
+            RequestBody::Json(json) => json.get("model").and_then(Value::as_str),
+            RequestBody::Multipart { model, .. } => *model,
+        }
+    }
 }
 
 impl OpenAIClientBuilder {
@@ -90,8 +164,14 @@ impl OpenAIClientBuilder {
         Self::default()
     }
 
-    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
-        self.api_key = Some(api_key.into());
+    pub fn with_api_key(self, api_key: impl Into<String>) -> Self {
+        self.with_credential_provider(StaticCredential::new(api_key))
+    }
+
+    /// Fetch the API key from `provider` before every request instead of
+    /// using a fixed key.
+    pub fn with_credential_provider(mut self, provider: impl CredentialProvider + 'static) -> Self {
+        self.credentials = Some(Arc::new(provider));
         self
     }
 
@@ -115,6 +195,62 @@ impl OpenAIClientBuilder {
         self
     }
 
+    /// Limit the time spent establishing each connection.
+    pub fn with_connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Use a pre-configured `reqwest::Client` for all requests.
+    ///
+    /// The client is used as-is, so `with_proxy`, `with_timeout` and
+    /// `with_connect_timeout` have no effect when this is set.
+    pub fn with_http_client(mut self, http_client: Client) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    /// Retry failed requests according to `retry_policy`.
+    ///
+    /// Without this, every request is attempted exactly once.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Wait for capacity in `rate_limiter` before sending requests.
+    ///
+    /// Only JSON requests that name a `model` are limited. The limiter can be
+    /// shared by several clients using the same API key.
+    pub fn with_rate_limiter(mut self, rate_limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Add a middleware to the stack run around every request.
+    pub fn with_middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Send requests to an Azure OpenAI resource instead of the OpenAI API.
+    ///
+    /// The endpoint set with `with_endpoint` is ignored. When `azure` has no
+    /// credentials, the key set with `with_api_key` or
+    /// `with_credential_provider` is sent in the `api-key` header.
+    pub fn with_azure(mut self, azure: AzureConfig) -> Self {
+        self.azure = Some(azure);
+        self
+    }
+
+    /// Default options for every call; see `OpenAIClient::with_options`.
+    /// An idempotency key is rejected by `build`, as it must identify a
+    /// single call.
+    pub fn with_request_options(mut self, options: RequestOptions) -> Self {
+        self.options = options;
+        self
+    }
+
     pub fn with_header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
         let headers = self.headers.get_or_insert_with(HeaderMap::new);
         headers.insert(
@@ -125,54 +261,141 @@ impl OpenAIClientBuilder {
     }
 
     pub fn build(self) -> Result<OpenAIClient, Box<dyn Error>> {
+        if self.options.idempotency_key.is_some() {
+            return Err(Box::new(APIError::InvalidRequest {
+                message: "an idempotency key identifies a single call; set it with OpenAIClient::with_options".to_string(),
+            }));
+        }
+
         let api_endpoint = self.api_endpoint.unwrap_or_else(|| {
             std::env::var("OPENAI_API_BASE").unwrap_or_else(|_| API_URL_V1.to_owned())
         });
 
+        let http_client = match self.http_client {
+            Some(http_client) => http_client,
+            None => {
+                Self::build_http_client(self.proxy.as_deref(), self.timeout, self.connect_timeout)?
+            }
+        };
+
         Ok(OpenAIClient {
             api_endpoint,
-            api_key: self.api_key,
+            credentials: self.credentials,
             organization: self.organization,
-            proxy: self.proxy,
-            timeout: self.timeout,
             headers: self.headers,
+            http_client,
+            retry_policy: self.retry_policy.unwrap_or_else(RetryPolicy::none),
+            rate_limiter: self.rate_limiter,
+            middleware: self.middleware,
+            azure: self.azure,
+            options: self.options,
+            idempotency_key: None,
         })
     }
-}
-
-impl OpenAIClient {
-    pub fn builder() -> OpenAIClientBuilder {
-        OpenAIClientBuilder::new()
-    }
-
-    async fn build_request(&self, method: Method, path: &str) -> reqwest::RequestBuilder {
-        let url = self
-            .build_url_with_preserved_query(path)
-            .unwrap_or_else(|_| format!("{}/{}", self.api_endpoint, path));
 
+    fn build_http_client(
+        proxy: Option<&str>,
+        timeout: Option<u64>,
+        connect_timeout: Option<Duration>,
+    ) -> Result<Client, reqwest::Error> {
         let client = Client::builder();
 
         #[cfg(feature = "rustls")]
         let client = client.use_rustls_tls();
 
-        let client = if let Some(timeout) = self.timeout {
+        let client = if let Some(timeout) = timeout {
             client.timeout(std::time::Duration::from_secs(timeout))
         } else {
             client
         };
 
-        let client = if let Some(proxy) = &self.proxy {
-            client.proxy(reqwest::Proxy::all(proxy).unwrap())
+        let client = if let Some(connect_timeout) = connect_timeout {
+            client.connect_timeout(connect_timeout)
+        } else {
+            client
+        };
+
+        let client = if let Some(proxy) = proxy {
+            client.proxy(reqwest::Proxy::all(proxy)?)
         } else {
             client
         };
 
-        let client = client.build().unwrap();
+        client.build()
+    }
+}
+
+impl OpenAIClient {
+    pub fn builder() -> OpenAIClientBuilder {
+        OpenAIClientBuilder::new()
+    }
+
+    /// A copy of this client that applies `options` to every call made
+    /// through it, layered over the options the client already has.
+    ///
+    /// The copy shares the connection pool, rate limiter and middleware.
+    ///
+    /// An idempotency key is single-use: it is sent with the first `POST`
+    /// or `DELETE` made through the copy, and with that call's retries;
+    /// reads do not use it. Later writes through the copy fail with
+    /// `APIError::InvalidRequest`, so make a new copy with a new key for
+    /// each call. Clones of the copy track the key separately.
+    pub fn with_options(&self, mut options: RequestOptions) -> Self {
+        let mut client = self.clone();
+        if let Some(key) = options.idempotency_key.take() {
+            client.idempotency_key = Some(IdempotencyKey {
+                key,
+                used: AtomicBool::new(false),
+            });
+        }
+        client.options = client.options.merge(options);
+        client
+    }
+
+    /// The idempotency key for a new call, if it is the write that the key
+    /// belongs to.
+    fn claim_idempotency_key(&self, method: &Method) -> Result<Option<String>, APIError> {
+        let Some(idempotency_key) = &self.idempotency_key else {
+            return Ok(None);
+        };
+        if *method == Method::GET || *method == Method::HEAD {
+            return Ok(None);
+        }
+        if idempotency_key.used.swap(true, Ordering::SeqCst) {
+            return Err(APIError::InvalidRequest {
+                message: format!(
+                    "idempotency key `{}` has already been used by an earlier call; \
+                     make a new client with OpenAIClient::with_options for each call",
+                    idempotency_key.key
+                ),
+            });
+        }
+        Ok(Some(idempotency_key.key.clone()))
+    }
+
+    async fn build_request(
+        &self,
+        method: Method,
+        path: &str,
+        model: Option<&str>,
+        idempotency_key: Option<&str>,
+    ) -> Result<reqwest::RequestBuilder, APIError> {
+        let url = match &self.azure {
+            Some(azure) => Self::build_azure_url(azure, path, model)?.to_string(),
+            None => self
+                .build_url_with_preserved_query(path)
+                .unwrap_or_else(|_| format!("{}/{}", self.api_endpoint, path)),
+        };
 
-        let mut request = client.request(method, url);
+        let mut request = self.http_client.request(method, url);
 
-        if let Some(api_key) = &self.api_key {
-            request = request.header("Authorization", format!("Bearer {api_key}"));
+        if let Some((provider, bearer)) = self.auth() {
+            let credential = provider.credential().await?;
+            request = if bearer {
+                request.header("Authorization", format!("Bearer {credential}"))
+            } else {
+                request.header("api-key", credential)
+            };
         }
 
         if let Some(organization) = &self.organization {
@@ -189,7 +412,217 @@ impl OpenAIClient {
             request = request.header("OpenAI-Beta", "assistants=v2");
         }
 
-        request
+        if let Some(idempotency_key) = idempotency_key {
+            request = request.header("Idempotency-Key", idempotency_key);
+        }
+
+        if !self.options.query.is_empty() {
+            request = request.query(&self.options.query);
+        }
+
+        Ok(request.headers(self.options.headers.clone()))
+    }
+
+    /// The credential provider in use, and whether its credential is sent as
+    /// a bearer token rather than in the Azure `api-key` header.
+    fn auth(&self) -> Option<(&Arc<dyn CredentialProvider>, bool)> {
+        match self.azure.as_ref().and_then(|azure| azure.auth.as_ref()) {
+            Some(AzureAuth::ApiKey(provider)) => Some((provider, false)),
+            Some(AzureAuth::BearerToken(provider)) => Some((provider, true)),
+            None => self
+                .credentials
+                .as_ref()
+                .map(|provider| (provider, self.azure.is_none())),
+        }
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: RequestBody<'_>,
+    ) -> Result<Response, APIError> {
+        self.send_request(method, path, body, false).await
+    }
+
+    /// Posts `body` with `"stream": true` and returns the response body as
+    /// a stream. The `timeout` option only covers the wait for the response
+    /// headers.
+    async fn post_stream(&self, path: &str, body: &impl Serialize) -> Result<ByteStream, APIError> {
+        let mut payload = Self::json_body(body)?;
+        if let RequestBody::Json(Value::Object(obj)) = &mut payload {
+            obj.insert("stream".into(), Value::Bool(true));
+        }
+
+        let response = self.send_request(Method::POST, path, payload, true).await?;
+        if response.status().is_success() {
+            Ok(self.byte_stream(response))
+        } else {
+            Err(Self::error_from_response(response).await)
+        }
+    }
+
+    async fn send_request(
+        &self,
+        method: Method,
+        path: &str,
+        mut body: RequestBody<'_>,
+        streaming: bool,
+    ) -> Result<Response, APIError> {
+        if let RequestBody::Json(Value::Object(json)) = &mut body {
+            json.extend(self.options.extra_body.clone());
+        }
+
+        let limited = match (&self.rate_limiter, &body) {
+            (Some(rate_limiter), RequestBody::Json(json)) => json
+                .get("model")
+                .and_then(Value::as_str)
+                .map(|model| (rate_limiter, model, estimate_tokens(json))),
+            _ => None,
+        };
+
+        let retry_policy = self
+            .options
+            .retry_policy
+            .as_ref()
+            .unwrap_or(&self.retry_policy);
+        let idempotency_key = self.claim_idempotency_key(&method)?;
+        let mut attempt = 1;
+        let mut refreshed_credentials = false;
+        loop {
+            if let Some((rate_limiter, model, tokens)) = limited {
+                rate_limiter.acquire(model, tokens).await;
+            }
+
+            let request = self
+                .build_request(
+                    method.clone(),
+                    path,
+                    body.model(),
+                    idempotency_key.as_deref(),
+                )
+                .await?;
+            let request = match &body {
+                RequestBody::Empty => request,
+                RequestBody::Json(json) => request.json(json),
+                RequestBody::Multipart { build_form, .. } => {
+                    let mut form = build_form()?;
+                    for (key, value) in &self.options.extra_body {
+                        form = match value {
+                            Value::String(value) => form.text(key.clone(), value.clone()),
+                            value => form.text(key.clone(), value.to_string()),
+                        };
+                    }
+                    request.multipart(form)
+                }
+            };
+            let request = match self.options.timeout {
+                Some(timeout) if !streaming => request.timeout(timeout),
+                _ => request,
+            };
+
+            let result = match self.options.timeout {
+                Some(timeout) if streaming => {
+                    tokio::time::timeout(timeout, self.execute(request.build()?, attempt))
+                        .await
+                        .map_err(|_| APIError::Timeout {
+                            message: format!("no response headers within {timeout:?}"),
+                        })??
+                }
+                _ => self.execute(request.build()?, attempt).await?,
+            };
+            if let (Some((rate_limiter, model, _)), Ok(response)) = (limited, &result) {
+                rate_limiter.update_from_headers(model, response.headers());
+            }
+
+            if matches!(&result, Ok(response) if response.status() == StatusCode::UNAUTHORIZED)
+                && !refreshed_credentials
+            {
+                refreshed_credentials = true;
+                if self
+                    .auth()
+                    .is_some_and(|(provider, _)| provider.invalidate())
+                {
+                    attempt += 1;
+                    continue;
+                }
+            }
+
+            let can_retry = attempt < retry_policy.max_attempts;
+            let delay = match result {
+                Ok(response)
+                    if can_retry && retry_policy.should_retry_status(response.status()) =>
+                {
+                    retry_policy.delay(attempt, Some(response.headers()))
+                }
+                Err(err) if can_retry && retry_policy.should_retry_error(&err) => {
+                    retry_policy.delay(attempt, None)
+                }
+                result => return Ok(result?),
+            };
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Sends a single attempt through the middleware stack.
+    async fn execute(
+        &self,
+        mut request: reqwest::Request,
+        attempt: u32,
+    ) -> Result<Result<Response, reqwest::Error>, APIError> {
+        for middleware in &self.middleware {
+            middleware.before_send(&mut request)?;
+        }
+
+        let info = RequestInfo {
+            method: request.method().clone(),
+            url: request.url().clone(),
+            attempt,
+        };
+        let start = Instant::now();
+        let result = self.http_client.execute(request).await;
+        let elapsed = start.elapsed();
+        for middleware in self.middleware.iter().rev() {
+            match &result {
+                Ok(response) => middleware.after_receive(&info, response, elapsed),
+                Err(err) => middleware.on_error(&info, err, elapsed),
+            }
+        }
+        Ok(result)
+    }
+
+    /// The response body as a stream of chunks, ending with
+    /// `APIError::Timeout` if the `stream_idle_timeout` option elapses
+    /// between two chunks.
+    fn byte_stream(&self, response: Response) -> ByteStream {
+        let chunks = response.bytes_stream().map_err(APIError::from);
+        let Some(idle_timeout) = self.options.stream_idle_timeout else {
+            return Box::pin(chunks);
+        };
+        Box::pin(stream::unfold(
+            Some(Box::pin(chunks)),
+            move |chunks| async move {
+                let mut chunks = chunks?;
+                match tokio::time::timeout(idle_timeout, chunks.next()).await {
+                    Ok(Some(chunk)) => Some((chunk, Some(chunks))),
+                    Ok(None) => None,
+                    Err(_) => Some((
+                        Err(APIError::Timeout {
+                            message: format!("no stream data received for {idle_timeout:?}"),
+                        }),
+                        None,
+                    )),
+                }
+            },
+        ))
+    }
+
+    fn json_body(body: &impl Serialize) -> Result<RequestBody<'static>, APIError> {
+        let json = to_value(body).map_err(|err| APIError::InvalidRequest {
+            message: format!("Failed to serialize request: {}", err),
+        })?;
+        Ok(RequestBody::Json(json))
     }
 
     async fn post<T: serde::de::DeserializeOwned>(
@@ -197,9 +630,9 @@ impl OpenAIClient {
         path: &str,
         body: &impl serde::ser::Serialize,
     ) -> Result<CallResponse<T>, APIError> {
-        let request = self.build_request(Method::POST, path).await;
-        let request = request.json(body);
-        let response = request.send().await?;
+        let response = self
+            .send(Method::POST, path, Self::json_body(body)?)
+            .await?;
         self.handle_response(response).await
     }
 
@@ -207,70 +640,90 @@ impl OpenAIClient {
         &self,
         path: &str,
     ) -> Result<CallResponse<T>, APIError> {
-        let request = self.build_request(Method::GET, path).await;
-        let response = request.send().await?;
+        let response = self.send(Method::GET, path, RequestBody::Empty).await?;
         self.handle_response(response).await
     }
 
     async fn get_raw(&self, path: &str) -> Result<Bytes, APIError> {
-        let request = self.build_request(Method::GET, path).await;
-        let response = request.send().await?;
-        Ok(response.bytes().await?)
+        let response = self.send(Method::GET, path, RequestBody::Empty).await?;
+        Self::handle_raw_response(response).await
     }
 
     async fn delete<T: serde::de::DeserializeOwned>(
         &self,
         path: &str,
     ) -> Result<CallResponse<T>, APIError> {
-        let request = self.build_request(Method::DELETE, path).await;
-        let response = request.send().await?;
+        let response = self.send(Method::DELETE, path, RequestBody::Empty).await?;
         self.handle_response(response).await
     }
 
     async fn post_form<T: serde::de::DeserializeOwned>(
         &self,
         path: &str,
-        form: Form,
+        model: Option<&str>,
+        build_form: &(dyn Fn() -> Result<Form, APIError> + Sync),
     ) -> Result<CallResponse<T>, APIError> {
-        let request = self.build_request(Method::POST, path).await;
-        let request = request.multipart(form);
-        let response = request.send().await?;
+        let response = self
+            .send(
+                Method::POST,
+                path,
+                RequestBody::Multipart { model, build_form },
+            )
+            .await?;
         self.handle_response(response).await
     }
 
-    async fn post_form_raw(&self, path: &str, form: Form) -> Result<Bytes, APIError> {
-        let request = self.build_request(Method::POST, path).await;
-        let request = request.multipart(form);
-        let response = request.send().await?;
-        Ok(response.bytes().await?)
+    async fn post_form_raw(
+        &self,
+        path: &str,
+        model: Option<&str>,
+        build_form: &(dyn Fn() -> Result<Form, APIError> + Sync),
+    ) -> Result<Bytes, APIError> {
+        let response = self
+            .send(
+                Method::POST,
+                path,
+                RequestBody::Multipart { model, build_form },
+            )
+            .await?;
+        Self::handle_raw_response(response).await
     }
 
     async fn handle_response<T: serde::de::DeserializeOwned>(
         &self,
         response: Response,
     ) -> Result<CallResponse<T>, APIError> {
-        let status = response.status();
-        if status.is_success() {
-            let headers = response.headers().clone();
-            let text = response.text().await.unwrap_or_else(|_| "".to_string());
-            match serde_json::from_str::<T>(&text) {
-                Ok(parsed) => Ok(CallResponse {
-                    headers,
-                    inner: parsed,
-                }),
-                Err(e) => Err(APIError::CustomError {
-                    message: format!("Failed to parse JSON: {e} / response {text}"),
-                }),
-            }
-        } else {
-            let error_message = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            Err(APIError::CustomError {
-                message: format!("{status}: {error_message}"),
-            })
+        if !response.status().is_success() {
+            return Err(Self::error_from_response(response).await);
+        }
+        let headers = response.headers().clone();
+        let text = response.text().await?;
+        match serde_json::from_str::<T>(&text) {
+            Ok(parsed) => Ok(CallResponse {
+                headers,
+                inner: parsed,
+            }),
+            Err(source) => Err(APIError::DeserializeError { source, body: text }),
+        }
+    }
+
+    async fn handle_raw_response(response: Response) -> Result<Bytes, APIError> {
+        if !response.status().is_success() {
+            return Err(Self::error_from_response(response).await);
         }
+        Ok(response.bytes().await?)
+    }
+
+    async fn error_from_response(response: Response) -> APIError {
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.text().await.unwrap_or_default();
+        APIError::ApiError(Box::new(ApiErrorResponse {
+            status,
+            error: ErrorObject::from_body(&body),
+            headers,
+            body,
+        }))
     }
 
     pub async fn completion(
@@ -312,16 +765,26 @@ impl OpenAIClient {
         self.post("embeddings", &req).await
     }
 
-    pub async fn file_list(&self) -> Result<CallResponse<FileListResponse>, APIError> {
-        self.get("files").await
+    pub async fn file_list(
+        &self,
+        params: ListParams,
+    ) -> Result<CallResponse<FileListResponse>, APIError> {
+        self.get(&params.to_path("files")).await
+    }
+
+    pub fn file_list_stream(
+        &self,
+        params: ListParams,
+    ) -> impl Stream<Item = Result<FileData, APIError>> + '_ {
+        self.paginate("files", params)
     }
 
     pub async fn upload_file(
         &self,
         req: FileUploadRequest,
     ) -> Result<CallResponse<FileUploadResponse>, APIError> {
-        let form = Self::create_form(&req, "file")?;
-        self.post_form("files", form).await
+        self.post_form("files", None, &|| Self::create_form(&req, "file"))
+            .await
     }
 
     pub async fn delete_file(
@@ -349,38 +812,95 @@ impl OpenAIClient {
         self.post("chat/completions", &req).await
     }
 
+    /// Asks for JSON matching the schema of `T` and parses the reply.
+    ///
+    /// Refusals, truncated output and invalid JSON are reported as
+    /// `APIError::ParseError`.
+    pub async fn chat_completion_parsed<T: StrictSchema + DeserializeOwned>(
+        &self,
+        mut req: ChatCompletionRequest,
+    ) -> Result<Parsed<T, ChatCompletionResponse>, APIError> {
+        req.response_format = Some(ResponseFormat::from_schema::<T>());
+        let response = self.chat_completion(req).await?.inner;
+        Ok(Parsed::from_chat_completion(response)?)
+    }
+
     pub async fn chat_completion_stream(
         &self,
         req: ChatCompletionStreamRequest,
-    ) -> Result<impl Stream<Item = ChatCompletionStreamResponse>, APIError> {
-        let mut payload = to_value(&req).map_err(|err| APIError::CustomError {
-            message: format!("Failed to serialize request: {}", err),
-        })?;
+    ) -> Result<ChatCompletionStream, APIError> {
+        let response = self.post_stream("chat/completions", &req).await?;
+        Ok(ChatCompletionStream::new(response))
+    }
 
-        if let Some(obj) = payload.as_object_mut() {
-            obj.insert("stream".into(), Value::Bool(true));
-        }
+    /// Like `chat_completion_stream`, but yields the raw chunks with every
+    /// choice, finish reasons and usage.
+    pub async fn chat_completion_chunk_stream(
+        &self,
+        req: ChatCompletionStreamRequest,
+    ) -> Result<ChatCompletionChunkStream, APIError> {
+        let response = self.post_stream("chat/completions", &req).await?;
+        Ok(ChatCompletionChunkStream::new(response))
+    }
+
+    /// Lists completions created with `store: true`.
+    pub async fn list_chat_completions(
+        &self,
+        filter: ChatCompletionListFilter,
+        params: ListParams,
+    ) -> Result<CallResponse<ChatCompletionList>, APIError> {
+        self.get(&params.to_path(&filter.to_path("chat/completions")))
+            .await
+    }
 
-        let request = self.build_request(Method::POST, "chat/completions").await;
-        let request = request.json(&payload);
-        let response = request.send().await?;
+    pub fn list_chat_completions_stream(
+        &self,
+        filter: ChatCompletionListFilter,
+        params: ListParams,
+    ) -> impl Stream<Item = Result<ChatCompletionResponse, APIError>> + '_ {
+        self.paginate(filter.to_path("chat/completions"), params)
+    }
 
-        if response.status().is_success() {
-            Ok(ChatCompletionStream {
-                response: Box::pin(response.bytes_stream()),
-                buffer: String::new(),
-                first_chunk: true,
-            })
-        } else {
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| String::from("Unknown error"));
+    pub async fn retrieve_chat_completion(
+        &self,
+        completion_id: String,
+    ) -> Result<CallResponse<ChatCompletionResponse>, APIError> {
+        self.get(&format!("chat/completions/{completion_id}")).await
+    }
 
-            Err(APIError::CustomError {
-                message: error_text,
-            })
-        }
+    pub async fn update_chat_completion(
+        &self,
+        completion_id: String,
+        req: UpdateChatCompletionRequest,
+    ) -> Result<CallResponse<ChatCompletionResponse>, APIError> {
+        self.post(&format!("chat/completions/{completion_id}"), &req)
+            .await
+    }
+
+    pub async fn delete_chat_completion(
+        &self,
+        completion_id: String,
+    ) -> Result<CallResponse<common::DeletionStatus>, APIError> {
+        self.delete(&format!("chat/completions/{completion_id}"))
+            .await
+    }
+
+    /// Lists the request messages of a stored completion.
+    pub async fn list_chat_completion_messages(
+        &self,
+        completion_id: String,
+        params: ListParams,
+    ) -> Result<CallResponse<ChatCompletionMessageList>, APIError> {
+        self.get(&params.to_path(&format!("chat/completions/{completion_id}/messages")))
+            .await
+    }
+
+    pub fn list_chat_completion_messages_stream(
+        &self,
+        completion_id: String,
+        params: ListParams,
+    ) -> impl Stream<Item = Result<StoredChatCompletionMessage, APIError>> + '_ {
+        self.paginate(format!("chat/completions/{completion_id}/messages"), params)
     }
 
     pub async fn audio_transcription(
@@ -390,22 +910,15 @@ impl OpenAIClient {
         // https://platform.openai.com/docs/api-reference/audio/createTranscription#audio-createtranscription-response_format
         if let Some(response_format) = &req.response_format {
             if response_format != "json" && response_format != "verbose_json" {
-                return Err(APIError::CustomError {
+                return Err(APIError::InvalidRequest {
                     message: "response_format must be either 'json' or 'verbose_json' please use audio_transcription_raw".to_string(),
                 });
             }
         }
-        let form: Form;
-        if req.clone().file.is_some() {
-            form = Self::create_form(&req, "file")?;
-        } else if let Some(bytes) = req.clone().bytes {
-            form = Self::create_form_from_bytes(&req, bytes)?;
-        } else {
-            return Err(APIError::CustomError {
-                message: "Either file or bytes must be provided".to_string(),
-            });
-        }
-        self.post_form("audio/transcriptions", form).await
+        self.post_form("audio/transcriptions", Some(&req.model), &|| {
+            Self::create_transcription_form(&req)
+        })
+        .await
     }
 
     pub async fn audio_transcription_raw(
@@ -415,67 +928,45 @@ impl OpenAIClient {
         // https://platform.openai.com/docs/api-reference/audio/createTranscription#audio-createtranscription-response_format
         if let Some(response_format) = &req.response_format {
             if response_format != "text" && response_format != "srt" && response_format != "vtt" {
-                return Err(APIError::CustomError {
+                return Err(APIError::InvalidRequest {
                     message: "response_format must be either 'text', 'srt' or 'vtt', please use audio_transcription".to_string(),
                 });
             }
         }
-        let form: Form;
-        if req.clone().file.is_some() {
-            form = Self::create_form(&req, "file")?;
-        } else if let Some(bytes) = req.clone().bytes {
-            form = Self::create_form_from_bytes(&req, bytes)?;
-        } else {
-            return Err(APIError::CustomError {
-                message: "Either file or bytes must be provided".to_string(),
-            });
-        }
-        self.post_form_raw("audio/transcriptions", form).await
+        self.post_form_raw("audio/transcriptions", Some(&req.model), &|| {
+            Self::create_transcription_form(&req)
+        })
+        .await
     }
 
     pub async fn audio_translation(
         &self,
         req: AudioTranslationRequest,
     ) -> Result<CallResponse<AudioTranslationResponse>, APIError> {
-        let form = Self::create_form(&req, "file")?;
-        self.post_form("audio/translations", form).await
+        self.post_form("audio/translations", Some(&req.model), &|| {
+            Self::create_form(&req, "file")
+        })
+        .await
     }
 
     pub async fn audio_speech(
         &self,
         req: AudioSpeechRequest,
     ) -> Result<CallResponse<AudioSpeechResponse>, APIError> {
-        let request = self.build_request(Method::POST, "audio/speech").await;
-        let request = request.json(&req);
-        let response = request.send().await?;
+        let response = self
+            .send(Method::POST, "audio/speech", Self::json_body(&req)?)
+            .await?;
+        if !response.status().is_success() {
+            return Err(Self::error_from_response(response).await);
+        }
         let headers = response.headers().clone();
         let bytes = response.bytes().await?;
         let path = Path::new(req.output.as_str());
         if let Some(parent) = path.parent() {
-            match create_dir_all(parent) {
-                Ok(_) => {}
-                Err(e) => {
-                    return Err(APIError::CustomError {
-                        message: e.to_string(),
-                    })
-                }
-            }
-        }
-        match File::create(path) {
-            Ok(mut file) => match file.write_all(&bytes) {
-                Ok(_) => {}
-                Err(e) => {
-                    return Err(APIError::CustomError {
-                        message: e.to_string(),
-                    })
-                }
-            },
-            Err(e) => {
-                return Err(APIError::CustomError {
-                    message: e.to_string(),
-                })
-            }
+            create_dir_all(parent)?;
         }
+        let mut file = File::create(path)?;
+        file.write_all(&bytes)?;
 
         Ok(CallResponse {
             headers,
@@ -492,21 +983,49 @@ impl OpenAIClient {
 
     pub async fn list_fine_tuning_jobs(
         &self,
+        params: ListParams,
     ) -> Result<CallResponse<FineTuningPagination<FineTuningJobObject>>, APIError> {
-        self.get("fine_tuning/jobs").await
+        self.get(&params.to_path("fine_tuning/jobs")).await
+    }
+
+    pub fn list_fine_tuning_jobs_stream(
+        &self,
+        params: ListParams,
+    ) -> impl Stream<Item = Result<FineTuningJobObject, APIError>> + '_ {
+        self.paginate("fine_tuning/jobs", params)
     }
 
     pub async fn list_fine_tuning_job_events(
         &self,
         req: ListFineTuningJobEventsRequest,
     ) -> Result<CallResponse<FineTuningPagination<FineTuningJobEvent>>, APIError> {
-        self.get(&format!(
+        let params = ListParams {
+            limit: req.limit,
+            after: req.after,
+            ..ListParams::default()
+        };
+        self.get(&params.to_path(&format!(
             "fine_tuning/jobs/{}/events",
             req.fine_tuning_job_id
-        ))
+        )))
         .await
     }
 
+    pub fn list_fine_tuning_job_events_stream(
+        &self,
+        req: ListFineTuningJobEventsRequest,
+    ) -> impl Stream<Item = Result<FineTuningJobEvent, APIError>> + '_ {
+        let params = ListParams {
+            limit: req.limit,
+            after: req.after,
+            ..ListParams::default()
+        };
+        self.paginate(
+            format!("fine_tuning/jobs/{}/events", req.fine_tuning_job_id),
+            params,
+        )
+    }
+
     pub async fn retrieve_fine_tuning_job(
         &self,
         req: RetrieveFineTuningJobRequest,
@@ -564,13 +1083,16 @@ impl OpenAIClient {
 
     pub async fn list_assistant(
         &self,
-        limit: Option<i64>,
-        order: Option<String>,
-        after: Option<String>,
-        before: Option<String>,
+        params: ListParams,
     ) -> Result<CallResponse<ListAssistant>, APIError> {
-        let url = Self::query_params(limit, order, after, before, "assistants".to_string());
-        self.get(&url).await
+        self.get(&params.to_path("assistants")).await
+    }
+
+    pub fn list_assistant_stream(
+        &self,
+        params: ListParams,
+    ) -> impl Stream<Item = Result<AssistantObject, APIError>> + '_ {
+        self.paginate("assistants", params)
     }
 
     pub async fn create_assistant_file(
@@ -603,19 +1125,18 @@ impl OpenAIClient {
     pub async fn list_assistant_file(
         &self,
         assistant_id: String,
-        limit: Option<i64>,
-        order: Option<String>,
-        after: Option<String>,
-        before: Option<String>,
+        params: ListParams,
     ) -> Result<CallResponse<ListAssistantFile>, APIError> {
-        let url = Self::query_params(
-            limit,
-            order,
-            after,
-            before,
-            format!("assistants/{assistant_id}/files"),
-        );
-        self.get(&url).await
+        self.get(&params.to_path(&format!("assistants/{assistant_id}/files")))
+            .await
+    }
+
+    pub fn list_assistant_file_stream(
+        &self,
+        assistant_id: String,
+        params: ListParams,
+    ) -> impl Stream<Item = Result<AssistantFileObject, APIError>> + '_ {
+        self.paginate(format!("assistants/{assistant_id}/files"), params)
     }
 
     pub async fn create_thread(
@@ -678,8 +1199,18 @@ impl OpenAIClient {
     pub async fn list_messages(
         &self,
         thread_id: String,
+        params: ListParams,
     ) -> Result<CallResponse<ListMessage>, APIError> {
-        self.get(&format!("threads/{thread_id}/messages")).await
+        self.get(&params.to_path(&format!("threads/{thread_id}/messages")))
+            .await
+    }
+
+    pub fn list_messages_stream(
+        &self,
+        thread_id: String,
+        params: ListParams,
+    ) -> impl Stream<Item = Result<MessageObject, APIError>> + '_ {
+        self.paginate(format!("threads/{thread_id}/messages"), params)
     }
 
     pub async fn retrieve_message_file(
@@ -698,19 +1229,22 @@ impl OpenAIClient {
         &self,
         thread_id: String,
         message_id: String,
-        limit: Option<i64>,
-        order: Option<String>,
-        after: Option<String>,
-        before: Option<String>,
+        params: ListParams,
     ) -> Result<CallResponse<ListMessageFile>, APIError> {
-        let url = Self::query_params(
-            limit,
-            order,
-            after,
-            before,
-            format!("threads/{thread_id}/messages/{message_id}/files"),
-        );
-        self.get(&url).await
+        self.get(&params.to_path(&format!("threads/{thread_id}/messages/{message_id}/files")))
+            .await
+    }
+
+    pub fn list_message_file_stream(
+        &self,
+        thread_id: String,
+        message_id: String,
+        params: ListParams,
+    ) -> impl Stream<Item = Result<MessageFileObject, APIError>> + '_ {
+        self.paginate(
+            format!("threads/{thread_id}/messages/{message_id}/files"),
+            params,
+        )
     }
 
     pub async fn create_run(
@@ -743,19 +1277,18 @@ impl OpenAIClient {
     pub async fn list_run(
         &self,
         thread_id: String,
-        limit: Option<i64>,
-        order: Option<String>,
-        after: Option<String>,
-        before: Option<String>,
+        params: ListParams,
     ) -> Result<CallResponse<ListRun>, APIError> {
-        let url = Self::query_params(
-            limit,
-            order,
-            after,
-            before,
-            format!("threads/{thread_id}/runs"),
-        );
-        self.get(&url).await
+        self.get(&params.to_path(&format!("threads/{thread_id}/runs")))
+            .await
+    }
+
+    pub fn list_run_stream(
+        &self,
+        thread_id: String,
+        params: ListParams,
+    ) -> impl Stream<Item = Result<RunObject, APIError>> + '_ {
+        self.paginate(format!("threads/{thread_id}/runs"), params)
     }
 
     pub async fn cancel_run(
@@ -793,19 +1326,19 @@ impl OpenAIClient {
         &self,
         thread_id: String,
         run_id: String,
-        limit: Option<i64>,
-        order: Option<String>,
-        after: Option<String>,
-        before: Option<String>,
+        params: ListParams,
     ) -> Result<CallResponse<ListRunStep>, APIError> {
-        let url = Self::query_params(
-            limit,
-            order,
-            after,
-            before,
-            format!("threads/{thread_id}/runs/{run_id}/steps"),
-        );
-        self.get(&url).await
+        self.get(&params.to_path(&format!("threads/{thread_id}/runs/{run_id}/steps")))
+            .await
+    }
+
+    pub fn list_run_step_stream(
+        &self,
+        thread_id: String,
+        run_id: String,
+        params: ListParams,
+    ) -> impl Stream<Item = Result<RunStepObject, APIError>> + '_ {
+        self.paginate(format!("threads/{thread_id}/runs/{run_id}/steps"), params)
     }
 
     pub async fn create_batch(
@@ -835,11 +1368,16 @@ impl OpenAIClient {
 
     pub async fn list_batch(
         &self,
-        after: Option<String>,
-        limit: Option<i64>,
+        params: ListParams,
     ) -> Result<CallResponse<ListBatchResponse>, APIError> {
-        let url = Self::query_params(limit, None, after, None, "batches".to_string());
-        self.get(&url).await
+        self.get(&params.to_path("batches")).await
+    }
+
+    pub fn list_batch_stream(
+        &self,
+        params: ListParams,
+    ) -> impl Stream<Item = Result<BatchResponse, APIError>> + '_ {
+        self.paginate("batches", params)
     }
 
     // Responses API
@@ -853,35 +1391,9 @@ impl OpenAIClient {
     pub async fn create_response_stream(
         &self,
         req: CreateResponseStreamRequest,
-    ) -> Result<impl Stream<Item = ResponseStreamResponse>, APIError> {
-        let mut payload = to_value(&req).map_err(|err| APIError::CustomError {
-            message: format!("Failed to serialize request: {}", err),
-        })?;
-
-        if let Some(obj) = payload.as_object_mut() {
-            obj.insert("stream".into(), Value::Bool(true));
-        }
-
-        let request = self.build_request(Method::POST, "responses").await;
-        let request = request.json(&payload);
-        let response = request.send().await?;
-
-        if response.status().is_success() {
-            Ok(ResponseStream {
-                response: Box::pin(response.bytes_stream()),
-                buffer: String::new(),
-                first_chunk: true,
-            })
-        } else {
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| String::from("Unknown error"));
-
-            Err(APIError::CustomError {
-                message: error_text,
-            })
-        }
+    ) -> Result<ResponseStream, APIError> {
+        let response = self.post_stream("responses", &req).await?;
+        Ok(ResponseStream::new(response))
     }
 
     pub async fn retrieve_response(
@@ -891,6 +1403,83 @@ impl OpenAIClient {
         self.get(&format!("responses/{response_id}")).await
     }
 
+    /// Like `chat_completion_parsed`, for the Responses API.
+    pub async fn create_response_parsed<T: StrictSchema + DeserializeOwned>(
+        &self,
+        mut req: CreateResponseRequest,
+    ) -> Result<Parsed<T, ResponseObject>, APIError> {
+        let mut text = req.text.take().unwrap_or_default();
+        text.format = Some(TextFormat::from_schema::<T>());
+        req.text = Some(text);
+        let response = self.create_response(req).await?.inner;
+        Ok(Parsed::from_response(response)?)
+    }
+
+    /// Starts a response that runs on the server after this call returns.
+    /// Follow it with `wait_for_response`, or stream it with
+    /// `resume_response_stream`.
+    pub async fn create_background_response(
+        &self,
+        mut req: CreateResponseRequest,
+    ) -> Result<CallResponse<ResponseObject>, APIError> {
+        req.background = Some(true);
+        self.create_response(req).await
+    }
+
+    /// Polls a response until it reaches a terminal status.
+    pub async fn wait_for_response(
+        &self,
+        response_id: &str,
+        poll: PollOptions,
+    ) -> Result<ResponseObject, APIError> {
+        let deadline = poll.timeout.map(|timeout| Instant::now() + timeout);
+        let mut delay = poll.interval;
+        loop {
+            let response = self.retrieve_response(response_id.to_string()).await?.inner;
+            if response.is_terminal() {
+                return Ok(response);
+            }
+            if let Some(deadline) = deadline {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    return Err(APIError::Timeout {
+                        message: format!(
+                            "response {response_id} is still {}",
+                            response.status.as_deref().unwrap_or("running")
+                        ),
+                    });
+                }
+                delay = delay.min(remaining);
+            }
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(poll.max_interval);
+        }
+    }
+
+    /// Streams a background response, continuing after the event numbered
+    /// `starting_after` when reconnecting to a dropped stream.
+    pub async fn resume_response_stream(
+        &self,
+        response_id: &str,
+        starting_after: Option<u64>,
+    ) -> Result<ResponseStream, APIError> {
+        let mut path = format!("responses/{response_id}?stream=true");
+        if let Some(sequence_number) = starting_after {
+            path.push_str(&format!("&starting_after={sequence_number}"));
+        }
+        let response = self
+            .send_request(Method::GET, &path, RequestBody::Empty, true)
+            .await?;
+        if !response.status().is_success() {
+            return Err(Self::error_from_response(response).await);
+        }
+        let stream = ResponseStream::new(self.byte_stream(response));
+        Ok(match starting_after {
+            Some(sequence_number) => stream.starting_after(sequence_number),
+            None => stream,
+        })
+    }
+
     pub async fn delete_response(
         &self,
         response_id: String,
@@ -912,25 +1501,18 @@ impl OpenAIClient {
     pub async fn list_response_input_items(
         &self,
         response_id: String,
-        after: Option<String>,
-        limit: Option<i64>,
-        order: Option<String>,
-    ) -> Result<CallResponse<ListResponses>, APIError> {
-        let mut url = format!("responses/{}/input_items", response_id);
-        let mut params = vec![];
-        if let Some(after) = after {
-            params.push(format!("after={}", after));
-        }
-        if let Some(limit) = limit {
-            params.push(format!("limit={}", limit));
-        }
-        if let Some(order) = order {
-            params.push(format!("order={}", order));
-        }
-        if !params.is_empty() {
-            url = format!("{}?{}", url, params.join("&"));
-        }
-        self.get(&url).await
+        params: ListParams,
+    ) -> Result<CallResponse<ResponseInputItemList>, APIError> {
+        self.get(&params.to_path(&format!("responses/{response_id}/input_items")))
+            .await
+    }
+
+    pub fn list_response_input_items_stream(
+        &self,
+        response_id: String,
+        params: ListParams,
+    ) -> impl Stream<Item = Result<InputItem, APIError>> + '_ {
+        self.paginate(format!("responses/{response_id}/input_items"), params)
     }
 
     pub async fn count_response_input_tokens(
@@ -940,6 +1522,257 @@ impl OpenAIClient {
         self.post("responses/input_tokens", &req).await
     }
 
+    // Conversations API
+    pub async fn create_conversation(
+        &self,
+        req: CreateConversationRequest,
+    ) -> Result<CallResponse<ConversationObject>, APIError> {
+        self.post("conversations", &req).await
+    }
+
+    pub async fn retrieve_conversation(
+        &self,
+        conversation_id: String,
+    ) -> Result<CallResponse<ConversationObject>, APIError> {
+        self.get(&format!("conversations/{conversation_id}")).await
+    }
+
+    pub async fn update_conversation(
+        &self,
+        conversation_id: String,
+        req: UpdateConversationRequest,
+    ) -> Result<CallResponse<ConversationObject>, APIError> {
+        self.post(&format!("conversations/{conversation_id}"), &req)
+            .await
+    }
+
+    pub async fn delete_conversation(
+        &self,
+        conversation_id: String,
+    ) -> Result<CallResponse<common::DeletionStatus>, APIError> {
+        self.delete(&format!("conversations/{conversation_id}"))
+            .await
+    }
+
+    pub async fn create_conversation_items(
+        &self,
+        conversation_id: String,
+        req: CreateConversationItemsRequest,
+    ) -> Result<CallResponse<ConversationItemList>, APIError> {
+        self.post(&format!("conversations/{conversation_id}/items"), &req)
+            .await
+    }
+
+    pub async fn list_conversation_items(
+        &self,
+        conversation_id: String,
+        params: ListParams,
+    ) -> Result<CallResponse<ConversationItemList>, APIError> {
+        self.get(&params.to_path(&format!("conversations/{conversation_id}/items")))
+            .await
+    }
+
+    pub fn list_conversation_items_stream(
+        &self,
+        conversation_id: String,
+        params: ListParams,
+    ) -> impl Stream<Item = Result<ConversationItem, APIError>> + '_ {
+        self.paginate(format!("conversations/{conversation_id}/items"), params)
+    }
+
+    pub async fn retrieve_conversation_item(
+        &self,
+        conversation_id: String,
+        item_id: String,
+    ) -> Result<CallResponse<ConversationItem>, APIError> {
+        self.get(&format!("conversations/{conversation_id}/items/{item_id}"))
+            .await
+    }
+
+    /// Removes an item and returns the updated conversation.
+    pub async fn delete_conversation_item(
+        &self,
+        conversation_id: String,
+        item_id: String,
+    ) -> Result<CallResponse<ConversationObject>, APIError> {
+        self.delete(&format!("conversations/{conversation_id}/items/{item_id}"))
+            .await
+    }
+
+    // Tool calling
+    /// Sends `req` with the registry's tools added and answers the model's
+    /// tool calls with the registered handlers until it replies without
+    /// calling a tool. Calls made in one turn run concurrently.
+    ///
+    /// Fails with `APIError::CustomError` if the model is still calling
+    /// tools after `registry.max_iterations` requests.
+    pub async fn run_tools(
+        &self,
+        mut req: ChatCompletionRequest,
+        registry: &ToolRegistry,
+    ) -> Result<ToolRun, APIError> {
+        if !registry.is_empty() {
+            let mut tools = req.tools.take().unwrap_or_default();
+            tools.extend(registry.tools());
+            req.tools = Some(tools);
+        }
+
+        for iteration in 1..=registry.max_iterations {
+            let response = self.chat_completion(req.clone()).await?.inner;
+            let Some(choice) = response.choices.first() else {
+                return Ok(ToolRun {
+                    messages: req.messages,
+                    response,
+                    iterations: iteration,
+                });
+            };
+            let calls = choice.message.tool_calls.clone().unwrap_or_default();
+            req.messages.push(ChatCompletionMessage {
+                role: MessageRole::assistant,
+                content: Content::Text(choice.message.content.clone().unwrap_or_default()),
+                name: None,
+                tool_calls: (!calls.is_empty()).then(|| calls.clone()),
+                tool_call_id: None,
+                audio: choice.message.audio.as_ref().map(|audio| audio.reference()),
+            });
+            if calls.is_empty() {
+                return Ok(ToolRun {
+                    messages: req.messages,
+                    response,
+                    iterations: iteration,
+                });
+            }
+
+            let outputs = registry
+                .call_all(calls.iter().map(|call| {
+                    (
+                        call.function.name.as_deref().unwrap_or_default(),
+                        call.function.arguments.as_deref().unwrap_or_default(),
+                    )
+                }))
+                .await;
+            for (call, output) in calls.iter().zip(outputs) {
+                req.messages.push(ChatCompletionMessage {
+                    role: MessageRole::tool,
+                    content: Content::Text(output),
+                    name: None,
+                    tool_calls: None,
+                    tool_call_id: Some(call.id.clone()),
+                    audio: None,
+                });
+            }
+        }
+        Err(Self::tool_loop_exceeded(registry))
+    }
+
+    /// Like `run_tools`, for the Responses API. Turns are chained with
+    /// `previous_response_id`, or through the conversation when one is set.
+    /// With `store: false` the whole transcript is sent on every turn, and
+    /// `reasoning.encrypted_content` is included so reasoning can be replayed.
+    pub async fn run_response_tools(
+        &self,
+        mut req: CreateResponseRequest,
+        registry: &ToolRegistry,
+    ) -> Result<ResponseToolRun, APIError> {
+        if !registry.is_empty() {
+            let mut tools = req.tools.take().unwrap_or_default();
+            tools.extend(registry.response_tools());
+            req.tools = Some(tools);
+        }
+
+        // Unstored reasoning can only be replayed from its encrypted content.
+        if req.store == Some(false) {
+            let include = req.include.get_or_insert_with(Vec::new);
+            if !include.iter().any(|item| item == ENCRYPTED_REASONING) {
+                include.push(ENCRYPTED_REASONING.to_string());
+            }
+        }
+
+        let mut items = match req.input.clone() {
+            Some(ResponseInput::Text(text)) => vec![InputItem::user(text)],
+            Some(ResponseInput::Items(items)) => items,
+            None => vec![],
+        };
+        for iteration in 1..=registry.max_iterations {
+            let response = self.create_response(req.clone()).await?.inner;
+            items.extend(response.output.iter().cloned().map(InputItem::from));
+            let calls: Vec<_> = response.function_calls().cloned().collect();
+            if calls.is_empty() {
+                return Ok(ResponseToolRun {
+                    items,
+                    response,
+                    iterations: iteration,
+                });
+            }
+
+            let outputs = registry
+                .call_all(
+                    calls
+                        .iter()
+                        .map(|call| (call.name.as_str(), call.arguments.as_str())),
+                )
+                .await;
+            let outputs: Vec<_> = calls
+                .iter()
+                .zip(outputs)
+                .map(|(call, output)| InputItem::function_call_output(&call.call_id, output))
+                .collect();
+            items.extend(outputs.iter().cloned());
+
+            if req.store == Some(false) {
+                let replay = items
+                    .iter()
+                    .filter(|item| match item {
+                        InputItem::Reasoning(reasoning) => reasoning.encrypted_content.is_some(),
+                        _ => true,
+                    })
+                    .cloned()
+                    .collect();
+                req.input = Some(ResponseInput::Items(replay));
+            } else {
+                if req.conversation.is_none() {
+                    req.previous_response_id = Some(response.id);
+                }
+                req.input = Some(ResponseInput::Items(outputs));
+            }
+        }
+        Err(Self::tool_loop_exceeded(registry))
+    }
+
+    fn tool_loop_exceeded(registry: &ToolRegistry) -> APIError {
+        APIError::CustomError {
+            message: format!(
+                "model was still calling tools after {} requests",
+                registry.max_iterations
+            ),
+        }
+    }
+
+    /// Lazily walks every page of a cursor-paginated list endpoint, starting
+    /// from `params` and following `after` cursors while `has_more` is set.
+    pub fn paginate<T>(
+        &self,
+        path: impl Into<String>,
+        params: ListParams,
+    ) -> impl Stream<Item = Result<T, APIError>> + '_
+    where
+        T: serde::de::DeserializeOwned + PageItem,
+    {
+        let path = path.into();
+        stream::try_unfold(Some(params), move |params| {
+            let path = path.clone();
+            async move {
+                let Some(params) = params else {
+                    return Ok::<_, APIError>(None);
+                };
+                let page: CursorPage<T> = self.get(&params.to_path(&path)).await?.inner;
+                let next = page.next_params(&params);
+                Ok(Some((stream::iter(page.data.into_iter().map(Ok)), next)))
+            }
+        })
+        .try_flatten()
+    }
+
     pub async fn list_models(&self) -> Result<CallResponse<ModelsResponse>, APIError> {
         self.get("models").await
     }
@@ -975,30 +1808,22 @@ impl OpenAIClient {
         Ok(url.to_string())
     }
 
-    fn query_params(
-        limit: Option<i64>,
-        order: Option<String>,
-        after: Option<String>,
-        before: Option<String>,
-        mut url: String,
-    ) -> String {
-        let mut params = vec![];
-        if let Some(limit) = limit {
-            params.push(format!("limit={limit}"));
-        }
-        if let Some(order) = order {
-            params.push(format!("order={order}"));
-        }
-        if let Some(after) = after {
-            params.push(format!("after={after}"));
-        }
-        if let Some(before) = before {
-            params.push(format!("before={before}"));
-        }
-        if !params.is_empty() {
-            url = format!("{}?{}", url, params.join("&"));
-        }
-        url
+    fn build_azure_url(
+        azure: &AzureConfig,
+        path: &str,
+        model: Option<&str>,
+    ) -> Result<Url, APIError> {
+        let full_path = format!(
+            "{}/openai/{}",
+            azure.endpoint.trim_end_matches('/'),
+            azure.path(path, model)
+        );
+        let mut url = Url::parse(&full_path).map_err(|err| APIError::InvalidRequest {
+            message: format!("Invalid Azure endpoint: {err}"),
+        })?;
+        url.query_pairs_mut()
+            .append_pair("api-version", &azure.api_version);
+        Ok(url)
     }
 
     fn is_beta(path: &str) -> bool {
@@ -1009,43 +1834,24 @@ impl OpenAIClient {
     where
         T: Serialize,
     {
-        let json = match serde_json::to_value(req) {
-            Ok(json) => json,
-            Err(e) => {
-                return Err(APIError::CustomError {
-                    message: e.to_string(),
-                })
-            }
-        };
+        let json = serde_json::to_value(req).map_err(|e| APIError::InvalidRequest {
+            message: e.to_string(),
+        })?;
         let file_path = if let Value::Object(map) = &json {
             map.get(file_field)
                 .and_then(|v| v.as_str())
-                .ok_or(APIError::CustomError {
+                .ok_or(APIError::InvalidRequest {
                     message: format!("Field '{file_field}' not found or not a string"),
                 })?
         } else {
-            return Err(APIError::CustomError {
+            return Err(APIError::InvalidRequest {
                 message: "Request is not a JSON object".to_string(),
             });
         };
 
-        let mut file = match File::open(file_path) {
-            Ok(file) => file,
-            Err(e) => {
-                return Err(APIError::CustomError {
-                    message: e.to_string(),
-                })
-            }
-        };
+        let mut file = File::open(file_path)?;
         let mut buffer = Vec::new();
-        match file.read_to_end(&mut buffer) {
-            Ok(_) => {}
-            Err(e) => {
-                return Err(APIError::CustomError {
-                    message: e.to_string(),
-                })
-            }
-        }
+        file.read_to_end(&mut buffer)?;
 
         let mut form =
             Form::new().part("file", Part::bytes(buffer).file_name(file_path.to_string()));
@@ -1069,18 +1875,25 @@ impl OpenAIClient {
         Ok(form)
     }
 
+    fn create_transcription_form(req: &AudioTranscriptionRequest) -> Result<Form, APIError> {
+        if req.file.is_some() {
+            Self::create_form(req, "file")
+        } else if let Some(bytes) = &req.bytes {
+            Self::create_form_from_bytes(req, bytes.clone())
+        } else {
+            Err(APIError::InvalidRequest {
+                message: "Either file or bytes must be provided".to_string(),
+            })
+        }
+    }
+
     fn create_form_from_bytes<T>(req: &T, bytes: Vec<u8>) -> Result<Form, APIError>
     where
         T: Serialize,
     {
-        let json = match serde_json::to_value(req) {
-            Ok(json) => json,
-            Err(e) => {
-                return Err(APIError::CustomError {
-                    message: e.to_string(),
-                })
-            }
-        };
+        let json = serde_json::to_value(req).map_err(|e| APIError::InvalidRequest {
+            message: e.to_string(),
+        })?;
 
         let mut form = Form::new().part("file", Part::bytes(bytes.clone()).file_name("file.mp3"));
 
@@ -1101,3 +1914,1178 @@ impl OpenAIClient {
         Ok(form)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v1::chat_completion::chat_completion_stream::ChatCompletionStreamResponse;
+    use crate::v1::error::ParseError;
+    use crate::v1::mock_server::{MockResponse, MockServer};
+    use crate::v1::responses::responses::ResponseTool;
+
+    #[test]
+    fn test_build_with_invalid_proxy_returns_error() {
+        let result = OpenAIClient::builder()
+            .with_api_key("sk-test")
+            .with_proxy("not a proxy url")
+            .build();
+        assert!(result.is_err());
+    }
+
+    fn retrying_client(url: &str) -> OpenAIClient {
+        OpenAIClient::builder()
+            .with_endpoint(url)
+            .with_api_key("sk-test")
+            .with_retry_policy(
+                RetryPolicy::new()
+                    .with_base_delay(std::time::Duration::from_millis(1))
+                    .with_jitter(false),
+            )
+            .build()
+            .unwrap()
+    }
+
+    fn model_json() -> serde_json::Value {
+        serde_json::json!({
+            "id": "gpt-4o",
+            "object": "model",
+            "created": 1,
+            "owned_by": "openai"
+        })
+    }
+
+    #[tokio::test]
+    async fn test_retries_transient_status() {
+        let server = MockServer::start(vec![
+            MockResponse::new(502, "bad gateway"),
+            MockResponse::new(429, "").header("retry-after-ms", "5"),
+            MockResponse::json(200, model_json()),
+        ])
+        .await;
+        let client = retrying_client(&server.url);
+
+        let result = client.retrieve_model("gpt-4o".to_string()).await.unwrap();
+        assert_eq!(result.inner.id.as_deref(), Some("gpt-4o"));
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        for request in requests {
+            assert_eq!(request.method, "GET");
+            assert_eq!(request.path, "/v1/models/gpt-4o");
+            assert_eq!(request.header("authorization"), Some("Bearer sk-test"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let server = MockServer::start(vec![
+            MockResponse::new(503, ""),
+            MockResponse::new(503, ""),
+            MockResponse::new(503, ""),
+            MockResponse::json(200, model_json()),
+        ])
+        .await;
+        let client = retrying_client(&server.url);
+
+        let err = client
+            .retrieve_model("gpt-4o".to_string())
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), Some(reqwest::StatusCode::SERVICE_UNAVAILABLE));
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_client_errors() {
+        let server = MockServer::start(vec![
+            MockResponse::json(
+                400,
+                serde_json::json!({"error": {"message": "bad", "type": "invalid_request_error"}}),
+            ),
+            MockResponse::json(200, model_json()),
+        ])
+        .await;
+        let client = retrying_client(&server.url);
+
+        let err = client
+            .retrieve_model("gpt-4o".to_string())
+            .await
+            .unwrap_err();
+        assert_eq!(err.error_type(), Some("invalid_request_error"));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_no_retry_by_default() {
+        let server = MockServer::start(vec![
+            MockResponse::new(500, ""),
+            MockResponse::json(200, model_json()),
+        ])
+        .await;
+        let client = OpenAIClient::builder()
+            .with_endpoint(&server.url)
+            .build()
+            .unwrap();
+
+        assert!(client.retrieve_model("gpt-4o".to_string()).await.is_err());
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retries_multipart_upload_with_rebuilt_form() {
+        let path = std::env::temp_dir().join("openai-api-rs-retry-upload.jsonl");
+        std::fs::write(&path, "{\"prompt\": \"hello\"}").unwrap();
+        let server = MockServer::start(vec![
+            MockResponse::new(500, ""),
+            MockResponse::json(
+                200,
+                serde_json::json!({
+                    "id": "file-1",
+                    "object": "file",
+                    "bytes": 19,
+                    "created_at": 1,
+                    "filename": "upload.jsonl",
+                    "purpose": "batch"
+                }),
+            ),
+        ])
+        .await;
+        let client = retrying_client(&server.url);
+
+        let req = FileUploadRequest::new(path.to_string_lossy().to_string(), "batch".to_string());
+        let result = client.upload_file(req).await.unwrap();
+        assert_eq!(result.inner.id, "file-1");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        for request in requests {
+            assert_eq!(request.path, "/v1/files");
+            let body = String::from_utf8_lossy(&request.body);
+            assert!(body.contains("{\"prompt\": \"hello\"}"));
+            assert!(body.contains("batch"));
+        }
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_learns_from_response_headers() {
+        let embedding = serde_json::json!({
+            "object": "list",
+            "data": [],
+            "model": "text-embedding-3-small",
+            "usage": {"prompt_tokens": 1, "total_tokens": 1}
+        });
+        let server = MockServer::start(vec![
+            MockResponse::json(200, embedding.clone())
+                .header("x-ratelimit-limit-requests", "60")
+                .header("x-ratelimit-remaining-requests", "0")
+                .header("x-ratelimit-reset-requests", "1m"),
+            MockResponse::json(200, embedding),
+        ])
+        .await;
+        let rate_limiter = Arc::new(RateLimiter::new());
+        let client = OpenAIClient::builder()
+            .with_endpoint(&server.url)
+            .with_rate_limiter(rate_limiter)
+            .build()
+            .unwrap();
+        let req = EmbeddingRequest::new(
+            "text-embedding-3-small".to_string(),
+            vec!["hello".to_string()],
+        );
+
+        client.embedding(req.clone()).await.unwrap();
+        let second =
+            tokio::time::timeout(std::time::Duration::from_millis(50), client.embedding(req));
+        assert!(second.await.is_err());
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[derive(Default)]
+    struct RecordingMiddleware {
+        events: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl Middleware for RecordingMiddleware {
+        fn before_send(&self, request: &mut reqwest::Request) -> Result<(), APIError> {
+            request
+                .headers_mut()
+                .insert("x-signature", HeaderValue::from_static("signed"));
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("before {}", request.url().path()));
+            Ok(())
+        }
+
+        fn after_receive(
+            &self,
+            request: &RequestInfo,
+            response: &Response,
+            _elapsed: std::time::Duration,
+        ) {
+            self.events.lock().unwrap().push(format!(
+                "after {} {} attempt {}",
+                request.url.path(),
+                response.status().as_u16(),
+                request.attempt
+            ));
+        }
+
+        fn on_error(
+            &self,
+            request: &RequestInfo,
+            _error: &reqwest::Error,
+            _elapsed: std::time::Duration,
+        ) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("error {}", request.url.path()));
+        }
+    }
+
+    struct RejectingMiddleware;
+
+    impl Middleware for RejectingMiddleware {
+        fn before_send(&self, _request: &mut reqwest::Request) -> Result<(), APIError> {
+            Err(APIError::InvalidRequest {
+                message: "rejected".to_string(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_middleware_wraps_every_attempt() {
+        let server = MockServer::start(vec![
+            MockResponse::new(500, ""),
+            MockResponse::json(200, model_json()),
+            MockResponse::new(200, "raw content"),
+        ])
+        .await;
+        let recorder = Arc::new(RecordingMiddleware::default());
+        let client = OpenAIClient::builder()
+            .with_endpoint(&server.url)
+            .with_retry_policy(
+                RetryPolicy::new()
+                    .with_base_delay(std::time::Duration::from_millis(1))
+                    .with_jitter(false),
+            )
+            .with_middleware(recorder.clone())
+            .build()
+            .unwrap();
+
+        client.retrieve_model("gpt-4o".to_string()).await.unwrap();
+        let content = client
+            .retrieve_file_content("file-1".to_string())
+            .await
+            .unwrap();
+        assert_eq!(content, Bytes::from("raw content"));
+
+        assert_eq!(
+            *recorder.events.lock().unwrap(),
+            vec![
+                "before /v1/models/gpt-4o",
+                "after /v1/models/gpt-4o 500 attempt 1",
+                "before /v1/models/gpt-4o",
+                "after /v1/models/gpt-4o 200 attempt 2",
+                "before /v1/files/file-1/content",
+                "after /v1/files/file-1/content 200 attempt 1",
+            ]
+        );
+        for request in server.requests() {
+            assert_eq!(request.header("x-signature"), Some("signed"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_middleware_on_error_and_rejection() {
+        let recorder = Arc::new(RecordingMiddleware::default());
+        let client = OpenAIClient::builder()
+            .with_endpoint("http://127.0.0.1:1/v1")
+            .with_middleware(recorder.clone())
+            .build()
+            .unwrap();
+        let err = client.list_models().await.unwrap_err();
+        assert!(matches!(err, APIError::ReqwestError(_)));
+        assert_eq!(
+            *recorder.events.lock().unwrap(),
+            vec!["before /v1/models", "error /v1/models"]
+        );
+
+        let server = MockServer::start(vec![]).await;
+        let client = OpenAIClient::builder()
+            .with_endpoint(&server.url)
+            .with_middleware(RejectingMiddleware)
+            .build()
+            .unwrap();
+        let err = client.list_models().await.unwrap_err();
+        assert!(matches!(err, APIError::InvalidRequest { .. }));
+        assert!(server.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_azure_deployment_routing_with_api_key() {
+        let server = MockServer::start(vec![
+            MockResponse::json(
+                200,
+                serde_json::json!({
+                    "object": "list",
+                    "data": [],
+                    "model": "text-embedding-3-small",
+                    "usage": {"prompt_tokens": 1, "total_tokens": 1}
+                }),
+            ),
+            MockResponse::json(200, serde_json::json!({"object": "list", "data": []})),
+        ])
+        .await;
+        let azure = AzureConfig::new(server.url.trim_end_matches("/v1"), "2024-10-21")
+            .with_deployment("text-embedding-3-small", "embed-prod")
+            .with_api_key("azure-key");
+        let client = OpenAIClient::builder().with_azure(azure).build().unwrap();
+
+        let req = EmbeddingRequest::new(
+            "text-embedding-3-small".to_string(),
+            vec!["hello".to_string()],
+        );
+        client.embedding(req).await.unwrap();
+        client.file_list(ListParams::new()).await.unwrap();
+
+        let requests = server.requests();
+        assert_eq!(
+            requests[0].path,
+            "/openai/deployments/embed-prod/embeddings?api-version=2024-10-21"
+        );
+        assert_eq!(requests[1].path, "/openai/files?api-version=2024-10-21");
+        for request in requests {
+            assert_eq!(request.header("api-key"), Some("azure-key"));
+            assert_eq!(request.header("authorization"), None);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_azure_multipart_uses_model_deployment_and_token() {
+        let server = MockServer::start(vec![MockResponse::json(
+            200,
+            serde_json::json!({"text": "hi"}),
+        )])
+        .await;
+        let azure = AzureConfig::new(server.url.trim_end_matches("/v1"), "2024-10-21")
+            .with_deployment("whisper-1", "whisper-prod")
+            .with_token_provider(StaticCredential::new("entra-token"));
+        let client = OpenAIClient::builder().with_azure(azure).build().unwrap();
+
+        let req = AudioTranscriptionRequest::new_bytes(vec![0; 4], "whisper-1".to_string());
+        let result = client.audio_transcription(req).await.unwrap();
+        assert_eq!(result.inner.text, "hi");
+
+        let request = &server.requests()[0];
+        assert_eq!(
+            request.path,
+            "/openai/deployments/whisper-prod/audio/transcriptions?api-version=2024-10-21"
+        );
+        assert_eq!(request.header("authorization"), Some("Bearer entra-token"));
+    }
+
+    struct RotatingKey(std::sync::atomic::AtomicUsize);
+
+    impl CredentialProvider for RotatingKey {
+        fn credential(&self) -> futures_util::future::BoxFuture<'_, Result<String, APIError>> {
+            let n = self.0.load(std::sync::atomic::Ordering::SeqCst);
+            Box::pin(async move { Ok(format!("sk-{n}")) })
+        }
+
+        fn invalidate(&self) -> bool {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn test_refreshes_credentials_once_on_unauthorized() {
+        let server = MockServer::start(vec![
+            MockResponse::new(401, ""),
+            MockResponse::json(200, model_json()),
+            MockResponse::new(401, ""),
+            MockResponse::new(401, ""),
+        ])
+        .await;
+        let client = OpenAIClient::builder()
+            .with_endpoint(&server.url)
+            .with_credential_provider(RotatingKey(std::sync::atomic::AtomicUsize::new(0)))
+            .build()
+            .unwrap();
+
+        client.retrieve_model("gpt-4o".to_string()).await.unwrap();
+        let err = client
+            .retrieve_model("gpt-4o".to_string())
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), Some(StatusCode::UNAUTHORIZED));
+
+        let keys: Vec<_> = server
+            .requests()
+            .iter()
+            .map(|request| request.header("authorization").unwrap().to_string())
+            .collect();
+        assert_eq!(
+            keys,
+            ["Bearer sk-0", "Bearer sk-1", "Bearer sk-1", "Bearer sk-2"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_static_key_is_not_retried_on_unauthorized() {
+        let server = MockServer::start(vec![MockResponse::new(401, "")]).await;
+        let client = retrying_client(&server.url);
+        assert!(client.retrieve_model("gpt-4o".to_string()).await.is_err());
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    fn file_json(id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "object": "file",
+            "bytes": 1,
+            "created_at": 1,
+            "filename": "data.jsonl",
+            "purpose": "batch"
+        })
+    }
+
+    #[tokio::test]
+    async fn test_list_stream_walks_all_pages() {
+        let server = MockServer::start(vec![
+            MockResponse::json(
+                200,
+                serde_json::json!({
+                    "object": "list",
+                    "data": [file_json("file-1"), file_json("file-2")],
+                    "has_more": true
+                }),
+            ),
+            MockResponse::json(
+                200,
+                serde_json::json!({
+                    "object": "list",
+                    "data": [file_json("file-3")],
+                    "first_id": "file-3",
+                    "last_id": "file-3",
+                    "has_more": false
+                }),
+            ),
+        ])
+        .await;
+        let client = retrying_client(&server.url);
+
+        let ids: Vec<String> = client
+            .file_list_stream(ListParams::new().with_limit(2))
+            .map_ok(|file| file.id)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(ids, ["file-1", "file-2", "file-3"]);
+
+        let paths: Vec<_> = server.requests().into_iter().map(|r| r.path).collect();
+        assert_eq!(
+            paths,
+            ["/v1/files?limit=2", "/v1/files?limit=2&after=file-2"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stored_chat_completions() {
+        let completion = |id: &str| {
+            let mut value = chat_json(
+                serde_json::json!({"role": "assistant", "content": "Hi"}),
+                "stop",
+            );
+            value["id"] = id.into();
+            value["metadata"] = serde_json::json!({"team": "eval"});
+            value
+        };
+        let server = MockServer::start(vec![
+            MockResponse::json(
+                200,
+                serde_json::json!({
+                    "object": "list",
+                    "data": [completion("chatcmpl-1")],
+                    "has_more": true
+                }),
+            ),
+            MockResponse::json(
+                200,
+                serde_json::json!({
+                    "object": "list",
+                    "data": [completion("chatcmpl-2")],
+                    "has_more": false
+                }),
+            ),
+            MockResponse::json(200, completion("chatcmpl-2")),
+            MockResponse::json(
+                200,
+                serde_json::json!({
+                    "object": "chat.completion.deleted",
+                    "id": "chatcmpl-2",
+                    "deleted": true
+                }),
+            ),
+        ])
+        .await;
+        let client = retrying_client(&server.url);
+
+        let filter = ChatCompletionListFilter::new()
+            .with_model("gpt-4o")
+            .with_metadata("team", "eval");
+        let ids: Vec<String> = client
+            .list_chat_completions_stream(filter, ListParams::new().with_limit(1))
+            .map_ok(|completion| completion.id.unwrap())
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(ids, ["chatcmpl-1", "chatcmpl-2"]);
+
+        let metadata = std::collections::HashMap::from([("team".to_string(), "prod".to_string())]);
+        let updated = client
+            .update_chat_completion(
+                "chatcmpl-2".to_string(),
+                UpdateChatCompletionRequest::new(metadata),
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.inner.metadata.as_ref().unwrap()["team"], "eval");
+        let deleted = client
+            .delete_chat_completion("chatcmpl-2".to_string())
+            .await
+            .unwrap();
+        assert!(deleted.inner.deleted);
+
+        let requests = server.requests();
+        let paths: Vec<_> = requests.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "/v1/chat/completions?model=gpt-4o&metadata%5Bteam%5D=eval&limit=1",
+                "/v1/chat/completions?model=gpt-4o&metadata%5Bteam%5D=eval&limit=1&after=chatcmpl-1",
+                "/v1/chat/completions/chatcmpl-2",
+                "/v1/chat/completions/chatcmpl-2",
+            ]
+        );
+        assert_eq!(requests[2].method, "POST");
+        assert_eq!(requests[3].method, "DELETE");
+    }
+
+    #[tokio::test]
+    async fn test_list_response_input_items_stream() {
+        let server = MockServer::start(vec![
+            MockResponse::json(
+                200,
+                serde_json::json!({
+                    "object": "list",
+                    "data": [{"type": "message", "id": "msg_1", "role": "user",
+                              "content": [{"type": "input_text", "text": "Hi"}]}],
+                    "first_id": "msg_1",
+                    "last_id": "msg_1",
+                    "has_more": true
+                }),
+            ),
+            MockResponse::json(
+                200,
+                serde_json::json!({
+                    "object": "list",
+                    "data": [{"type": "function_call_output", "id": "fco_1",
+                              "call_id": "call_1", "output": "42"}],
+                    "has_more": false
+                }),
+            ),
+        ])
+        .await;
+        let client = retrying_client(&server.url);
+
+        let items: Vec<InputItem> = client
+            .list_response_input_items_stream("resp_1".to_string(), ListParams::new())
+            .try_collect()
+            .await
+            .unwrap();
+        assert!(matches!(&items[0], InputItem::Message(m) if m.id.as_deref() == Some("msg_1")));
+        assert!(
+            matches!(&items[1], InputItem::FunctionCallOutput { output, .. } if output == "42")
+        );
+        assert_eq!(
+            server.requests()[1].path,
+            "/v1/responses/resp_1/input_items?after=msg_1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_stream_stops_on_error() {
+        let server = MockServer::start(vec![MockResponse::new(400, "")]).await;
+        let client = retrying_client(&server.url);
+
+        let results: Vec<_> = client
+            .list_batch_stream(ListParams::new())
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+    }
+
+    #[tokio::test]
+    async fn test_request_options_apply_to_scoped_client() {
+        let server = MockServer::start(vec![
+            MockResponse::json(
+                200,
+                serde_json::json!({
+                    "object": "list",
+                    "data": [],
+                    "model": "text-embedding-3-small",
+                    "usage": {"prompt_tokens": 1, "total_tokens": 1}
+                }),
+            ),
+            MockResponse::json(200, model_json()),
+            MockResponse::json(200, model_json()),
+        ])
+        .await;
+        let client = retrying_client(&server.url);
+        let scoped = client.with_options(
+            RequestOptions::new()
+                .with_header("x-trace-id", "trace-1")
+                .with_query("route", "eu")
+                .with_extra_body("user", "user-1".into())
+                .with_idempotency_key("idem-1"),
+        );
+
+        let req = EmbeddingRequest::new(
+            "text-embedding-3-small".to_string(),
+            vec!["hello".to_string()],
+        );
+        scoped.embedding(req.clone()).await.unwrap();
+        client.retrieve_model("gpt-4o".to_string()).await.unwrap();
+        scoped.retrieve_model("gpt-4o".to_string()).await.unwrap();
+        let err = scoped.embedding(req).await.unwrap_err();
+        assert!(
+            matches!(&err, APIError::InvalidRequest { message } if message.contains("`idem-1` has already been used")),
+            "{err}"
+        );
+
+        let requests = server.requests();
+        assert_eq!(requests[0].path, "/v1/embeddings?route=eu");
+        assert_eq!(requests[0].header("x-trace-id"), Some("trace-1"));
+        assert_eq!(requests[0].header("idempotency-key"), Some("idem-1"));
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["user"], "user-1");
+        assert_eq!(body["model"], "text-embedding-3-small");
+
+        assert_eq!(requests[1].path, "/v1/models/gpt-4o");
+        assert_eq!(requests[1].header("x-trace-id"), None);
+        assert_eq!(requests[2].header("x-trace-id"), Some("trace-1"));
+        assert_eq!(requests[2].header("idempotency-key"), None);
+        assert_eq!(requests.len(), 3);
+
+        let built = OpenAIClient::builder()
+            .with_request_options(RequestOptions::new().with_idempotency_key("idem-2"))
+            .build();
+        assert!(built.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_is_kept_across_retries() {
+        let server = MockServer::start(vec![
+            MockResponse::json(503, serde_json::json!({})),
+            MockResponse::json(
+                200,
+                serde_json::json!({"id": "chatcmpl-1", "object": "chat.completion.deleted", "deleted": true}),
+            ),
+            MockResponse::json(
+                200,
+                serde_json::json!({"id": "chatcmpl-1", "object": "chat.completion.deleted", "deleted": true}),
+            ),
+        ])
+        .await;
+        let client = retrying_client(&server.url)
+            .with_options(RequestOptions::new().with_idempotency_key("idem-1"));
+        let copy = client.clone();
+
+        client
+            .delete_chat_completion("chatcmpl-1".to_string())
+            .await
+            .unwrap();
+        copy.delete_chat_completion("chatcmpl-1".to_string())
+            .await
+            .unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert!(requests
+            .iter()
+            .all(|request| request.header("idempotency-key") == Some("idem-1")));
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout_option() {
+        let server = MockServer::start(vec![
+            MockResponse::json(200, model_json()).delay(std::time::Duration::from_millis(500))
+        ])
+        .await;
+        let client = retrying_client(&server.url).with_options(
+            RequestOptions::new()
+                .with_timeout(std::time::Duration::from_millis(50))
+                .with_retry_policy(RetryPolicy::none()),
+        );
+
+        let err = client
+            .retrieve_model("gpt-4o".to_string())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, APIError::ReqwestError(ref err) if err.is_timeout()));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_stream_idle_timeout_ends_stalled_stream() {
+        let server = MockServer::start(vec![MockResponse::new(
+            200,
+            "data: {\"choices\":[{\"delta\":{\"content\":\"hi\"}}]}\n\n",
+        )
+        .header("content-type", "text/event-stream")
+        .hold_open(std::time::Duration::from_secs(10))])
+        .await;
+        let client = OpenAIClient::builder()
+            .with_endpoint(&server.url)
+            .with_request_options(
+                RequestOptions::new()
+                    .with_stream_idle_timeout(std::time::Duration::from_millis(100)),
+            )
+            .build()
+            .unwrap();
+
+        let req = ChatCompletionStreamRequest::new("gpt-4o".to_string(), vec![]);
+        let stream = client.chat_completion_stream(req).await.unwrap();
+        let items = tokio::time::timeout(
+            std::time::Duration::from_secs(2),
+            stream.collect::<Vec<_>>(),
+        )
+        .await
+        .unwrap();
+        assert!(matches!(
+            items.as_slice(),
+            [
+                Ok(ChatCompletionStreamResponse::Content(content)),
+                Err(APIError::Timeout { .. })
+            ] if content == "hi"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_background_response() {
+        let running = serde_json::json!({"id": "resp_1", "object": "response", "status": "queued"});
+        let done = serde_json::json!({"id": "resp_1", "object": "response", "status": "completed"});
+        let server = MockServer::start(vec![
+            MockResponse::json(200, running.clone()),
+            MockResponse::json(200, running),
+            MockResponse::json(200, done),
+        ])
+        .await;
+        let client = retrying_client(&server.url);
+
+        let mut req = CreateResponseRequest::new();
+        req.input = Some("Research this".into());
+        let created = client.create_background_response(req).await.unwrap();
+        let poll = PollOptions::new().with_interval(std::time::Duration::from_millis(10));
+        let response = client
+            .wait_for_response(&created.inner.id, poll)
+            .await
+            .unwrap();
+
+        assert_eq!(response.status.as_deref(), Some("completed"));
+        let requests = server.requests();
+        let body: Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["background"], true);
+        assert_eq!(requests[2].method, "GET");
+        assert_eq!(requests[2].path, "/v1/responses/resp_1");
+    }
+
+    #[tokio::test]
+    async fn test_resume_response_stream() {
+        let events = "data: {\"type\":\"response.output_text.delta\",\"sequence_number\":8,\"item_id\":\"msg_1\",\"output_index\":0,\"delta\":\"lo\"}\n\n\
+                      data: [DONE]\n\n";
+        let server = MockServer::start(vec![
+            MockResponse::new(200, events).header("content-type", "text/event-stream")
+        ])
+        .await;
+        let client = retrying_client(&server.url);
+
+        let mut stream = client
+            .resume_response_stream("resp_1", Some(6))
+            .await
+            .unwrap();
+        while stream.next().await.is_some() {}
+
+        assert_eq!(
+            server.requests()[0].path,
+            "/v1/responses/resp_1?stream=true&starting_after=6"
+        );
+        assert_eq!(stream.last_sequence_number(), Some(8));
+        assert_eq!(stream.gaps(), &[std::ops::Range { start: 7, end: 8 }]);
+    }
+
+    #[tokio::test]
+    async fn test_conversation_items_roundtrip() {
+        let server = MockServer::start(vec![
+            MockResponse::json(
+                200,
+                serde_json::json!({"id": "conv_1", "object": "conversation", "created_at": 1}),
+            ),
+            MockResponse::json(
+                200,
+                serde_json::json!({
+                    "object": "list",
+                    "data": [{"type": "message", "id": "msg_1", "role": "user", "content": "Hi"}],
+                    "has_more": false
+                }),
+            ),
+        ])
+        .await;
+        let client = retrying_client(&server.url);
+
+        let conversation = client
+            .create_conversation(CreateConversationRequest::new().metadata(
+                std::collections::HashMap::from([("topic".to_string(), "demo".to_string())]),
+            ))
+            .await
+            .unwrap();
+        let items: Vec<_> = client
+            .list_conversation_items_stream(conversation.inner.id, ListParams::new())
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(items.len(), 1);
+        let requests = server.requests();
+        let body: Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body, serde_json::json!({"metadata": {"topic": "demo"}}));
+        assert_eq!(requests[1].path, "/v1/conversations/conv_1/items");
+    }
+
+    fn echo_registry() -> ToolRegistry {
+        let mut registry = ToolRegistry::new().with_max_iterations(3);
+        registry.register(
+            crate::v1::types::Function {
+                name: "echo".to_string(),
+                description: None,
+                parameters: crate::v1::types::FunctionParameters {
+                    schema_type: crate::v1::types::JSONSchemaType::Object,
+                    properties: None,
+                    required: None,
+                    ..Default::default()
+                },
+                strict: None,
+            },
+            |args: Value| async move { Ok::<_, String>(args["text"].clone()) },
+        );
+        registry
+    }
+
+    fn chat_json(message: serde_json::Value, finish_reason: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 1,
+            "model": "gpt-4o",
+            "choices": [{"index": 0, "message": message, "finish_reason": finish_reason}],
+            "usage": {"prompt_tokens": 1, "completion_tokens": 1, "total_tokens": 2}
+        })
+    }
+
+    #[tokio::test]
+    async fn test_run_tools_answers_tool_calls() {
+        let tool_calls = serde_json::json!({
+            "role": "assistant",
+            "content": null,
+            "tool_calls": [
+                {"id": "call_1", "type": "function",
+                 "function": {"name": "echo", "arguments": "{\"text\":\"a\"}"}},
+                {"id": "call_2", "type": "function",
+                 "function": {"name": "nope", "arguments": "{}"}}
+            ]
+        });
+        let server = MockServer::start(vec![
+            MockResponse::json(200, chat_json(tool_calls, "tool_calls")),
+            MockResponse::json(
+                200,
+                chat_json(
+                    serde_json::json!({"role": "assistant", "content": "done"}),
+                    "stop",
+                ),
+            ),
+        ])
+        .await;
+        let client = retrying_client(&server.url);
+
+        let req = ChatCompletionRequest::new(
+            "gpt-4o".to_string(),
+            vec![ChatCompletionMessage {
+                role: MessageRole::user,
+                content: Content::Text("echo a".to_string()),
+                name: None,
+                tool_calls: None,
+                tool_call_id: None,
+                audio: None,
+            }],
+        );
+        let run = client.run_tools(req, &echo_registry()).await.unwrap();
+
+        assert_eq!(run.iterations, 2);
+        assert_eq!(run.messages.len(), 5);
+        assert_eq!(
+            run.response.choices[0].message.content.as_deref(),
+            Some("done")
+        );
+        let body: Value = serde_json::from_slice(&server.requests()[1].body).unwrap();
+        assert_eq!(body["tools"][0]["function"]["name"], "echo");
+        assert_eq!(
+            body["messages"][2],
+            serde_json::json!({"role": "tool", "content": "a", "tool_call_id": "call_1"})
+        );
+        assert_eq!(body["messages"][3]["content"], "Error: unknown tool `nope`");
+    }
+
+    #[tokio::test]
+    async fn test_run_response_tools_stops_at_max_iterations() {
+        let call = serde_json::json!({
+            "id": "resp_1",
+            "object": "response",
+            "status": "completed",
+            "output": [{"type": "function_call", "id": "fc_1", "call_id": "call_1",
+                        "name": "echo", "arguments": "{\"text\":\"b\"}"}]
+        });
+        let server = MockServer::start(vec![MockResponse::json(200, call); 3]).await;
+        let client = retrying_client(&server.url);
+
+        let mut req = CreateResponseRequest::new();
+        req.input = Some("loop forever".into());
+        req.tools = Some(vec![ResponseTool::Other(
+            serde_json::json!({"type": "web_search"}),
+        )]);
+        let err = client
+            .run_response_tools(req, &echo_registry())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, APIError::CustomError { .. }));
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        let body: Value = serde_json::from_slice(&requests[1].body).unwrap();
+        assert_eq!(body["tools"][0]["type"], "web_search");
+        assert_eq!(body["tools"][1]["type"], "function");
+        assert_eq!(body["tools"][1]["name"], "echo");
+        assert_eq!(body["previous_response_id"], "resp_1");
+        assert_eq!(
+            body["input"],
+            serde_json::json!([{"type": "function_call_output", "call_id": "call_1", "output": "b"}])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_response_tools_without_store_replays_transcript() {
+        let call = serde_json::json!({
+            "id": "resp_1",
+            "object": "response",
+            "status": "completed",
+            "output": [
+                {"type": "reasoning", "id": "rs_1", "summary": [], "encrypted_content": "gAAA"},
+                {"type": "reasoning", "id": "rs_2", "summary": []},
+                {"type": "function_call", "id": "fc_1", "call_id": "call_1",
+                 "name": "echo", "arguments": "{\"text\":\"b\"}"}
+            ]
+        });
+        let done = serde_json::json!({
+            "id": "resp_2",
+            "object": "response",
+            "status": "completed",
+            "output": []
+        });
+        let server = MockServer::start(vec![
+            MockResponse::json(200, call),
+            MockResponse::json(200, done),
+        ])
+        .await;
+        let client = retrying_client(&server.url);
+
+        let mut req = CreateResponseRequest::new();
+        req.input = Some("echo b".into());
+        req.store = Some(false);
+        let run = client
+            .run_response_tools(req, &echo_registry())
+            .await
+            .unwrap();
+
+        assert_eq!(run.iterations, 2);
+        assert_eq!(run.items.len(), 5);
+        let requests = server.requests();
+        let first: Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(
+            first["include"],
+            serde_json::json!(["reasoning.encrypted_content"])
+        );
+        let second: Value = serde_json::from_slice(&requests[1].body).unwrap();
+        assert!(second.get("previous_response_id").is_none());
+        let types: Vec<_> = second["input"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["type"].as_str().unwrap_or("message"))
+            .collect();
+        assert_eq!(
+            types,
+            [
+                "message",
+                "reasoning",
+                "function_call",
+                "function_call_output"
+            ]
+        );
+        assert_eq!(second["input"][1]["encrypted_content"], "gAAA");
+    }
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Answer {
+        value: i64,
+    }
+
+    impl StrictSchema for Answer {
+        fn schema_name() -> String {
+            "Answer".to_string()
+        }
+
+        fn json_schema() -> Value {
+            serde_json::json!({
+                "type": "object",
+                "properties": {"value": {"type": "integer"}}
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion_parsed() {
+        let server = MockServer::start(vec![
+            MockResponse::json(
+                200,
+                chat_json(
+                    serde_json::json!({"role": "assistant", "content": "{\"value\":42}"}),
+                    "stop",
+                ),
+            ),
+            MockResponse::json(
+                200,
+                chat_json(
+                    serde_json::json!({"role": "assistant", "content": null, "refusal": "No."}),
+                    "stop",
+                ),
+            ),
+            MockResponse::json(
+                200,
+                chat_json(
+                    serde_json::json!({"role": "assistant", "content": "{\"val"}),
+                    "length",
+                ),
+            ),
+        ])
+        .await;
+        let client = retrying_client(&server.url);
+        let req = ChatCompletionRequest::new("gpt-4o".to_string(), vec![]);
+
+        let parsed = client
+            .chat_completion_parsed::<Answer>(req.clone())
+            .await
+            .unwrap();
+        assert_eq!(parsed.parsed.value, 42);
+        let body: Value = serde_json::from_slice(&server.requests()[0].body).unwrap();
+        assert_eq!(body["response_format"]["type"], "json_schema");
+        assert_eq!(body["response_format"]["json_schema"]["name"], "Answer");
+        assert_eq!(
+            body["response_format"]["json_schema"]["schema"]["required"],
+            serde_json::json!(["value"])
+        );
+
+        let err = client
+            .chat_completion_parsed::<Answer>(req.clone())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, APIError::ParseError(ParseError::Refusal(r)) if r == "No."));
+
+        let err = client
+            .chat_completion_parsed::<Answer>(req)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, APIError::ParseError(ParseError::Incomplete { reason }) if reason == "length")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_response_parsed() {
+        let message = |text: &str| {
+            serde_json::json!({
+                "id": "resp_1",
+                "object": "response",
+                "status": "completed",
+                "output": [{"type": "message", "id": "msg_1", "role": "assistant",
+                            "content": [{"type": "output_text", "text": text, "annotations": []}]}]
+            })
+        };
+        let server = MockServer::start(vec![
+            MockResponse::json(200, message("{\"value\":7}")),
+            MockResponse::json(200, message("not json")),
+            MockResponse::json(
+                200,
+                serde_json::json!({
+                    "id": "resp_2",
+                    "object": "response",
+                    "status": "incomplete",
+                    "incomplete_details": {"reason": "max_output_tokens"},
+                    "output": []
+                }),
+            ),
+        ])
+        .await;
+        let client = retrying_client(&server.url);
+        let mut req = CreateResponseRequest::new();
+        req.input = Some("seven".into());
+
+        let parsed = client
+            .create_response_parsed::<Answer>(req.clone())
+            .await
+            .unwrap();
+        assert_eq!(parsed.parsed.value, 7);
+        let body: Value = serde_json::from_slice(&server.requests()[0].body).unwrap();
+        assert_eq!(body["text"]["format"]["type"], "json_schema");
+        assert_eq!(body["text"]["format"]["strict"], true);
+
+        let err = client
+            .create_response_parsed::<Answer>(req.clone())
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, APIError::ParseError(ParseError::Json { content, .. }) if content == "not json")
+        );
+
+        let err = client
+            .create_response_parsed::<Answer>(req)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, APIError::ParseError(ParseError::Incomplete { reason }) if reason == "max_output_tokens")
+        );
+    }
+
+    #[test]
+    fn test_build_with_custom_http_client() {
+        let http_client = Client::builder().build().unwrap();
+        let client = OpenAIClient::builder()
+            .with_endpoint("http://localhost:8080/v1")
+            .with_http_client(http_client)
+            .with_proxy("not a proxy url")
+            .build();
+        assert!(client.is_ok());
+    }
+}
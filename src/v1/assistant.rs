@@ -3,6 +3,7 @@ use std::collections::HashMap;
 
 use super::types;
 use crate::impl_builder_methods;
+use crate::v1::pagination::{CursorPage, PageItem};
 
 #[derive(Debug, Serialize, Clone)]
 pub struct AssistantRequest {
@@ -95,10 +96,12 @@ pub struct VectorStores {
     pub metadata: Option<HashMap<String, String>>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-pub struct ListAssistant {
-    pub object: String,
-    pub data: Vec<AssistantObject>,
+pub type ListAssistant = CursorPage<AssistantObject>;
+
+impl PageItem for AssistantObject {
+    fn page_id(&self) -> &str {
+        &self.id
+    }
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -114,8 +117,10 @@ pub struct AssistantFileObject {
     pub assistant_id: String,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-pub struct ListAssistantFile {
-    pub object: String,
-    pub data: Vec<AssistantFileObject>,
+pub type ListAssistantFile = CursorPage<AssistantFileObject>;
+
+impl PageItem for AssistantFileObject {
+    fn page_id(&self) -> &str {
+        &self.id
+    }
 }
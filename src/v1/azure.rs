@@ -0,0 +1,150 @@
+use crate::v1::credentials::{CredentialProvider, StaticCredential};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub enum AzureAuth {
+    /// Sent in the `api-key` header.
+    ApiKey(Arc<dyn CredentialProvider>),
+    /// A Microsoft Entra ID token, sent as `Authorization: Bearer <token>`.
+    BearerToken(Arc<dyn CredentialProvider>),
+}
+
+/// Settings for talking to an Azure OpenAI resource.
+///
+/// Model-scoped endpoints (chat, completions, embeddings, images and audio)
+/// are sent to `{endpoint}/openai/deployments/{deployment}/...`, where the
+/// deployment is looked up from the request's `model`. Models without a
+/// mapping use the default deployment, or the model name itself when no
+/// default is set. Other endpoints such as files and batches are sent to
+/// `{endpoint}/openai/...`. Every request carries the `api-version` query
+/// parameter.
+#[derive(Debug, Clone)]
+pub struct AzureConfig {
+    pub endpoint: String,
+    pub api_version: String,
+    pub deployments: HashMap<String, String>,
+    pub default_deployment: Option<String>,
+    pub auth: Option<AzureAuth>,
+}
+
+impl AzureConfig {
+    /// `endpoint` is the resource URL, e.g. `https://my-resource.openai.azure.com`.
+    pub fn new(endpoint: impl Into<String>, api_version: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            api_version: api_version.into(),
+            deployments: HashMap::new(),
+            default_deployment: None,
+            auth: None,
+        }
+    }
+
+    /// Configuration for `https://{resource}.openai.azure.com`.
+    pub fn from_resource(resource: &str, api_version: impl Into<String>) -> Self {
+        Self::new(format!("https://{resource}.openai.azure.com"), api_version)
+    }
+
+    pub fn with_deployment(
+        mut self,
+        model: impl Into<String>,
+        deployment: impl Into<String>,
+    ) -> Self {
+        self.deployments.insert(model.into(), deployment.into());
+        self
+    }
+
+    pub fn with_default_deployment(mut self, deployment: impl Into<String>) -> Self {
+        self.default_deployment = Some(deployment.into());
+        self
+    }
+
+    pub fn with_api_key(self, api_key: impl Into<String>) -> Self {
+        self.with_api_key_provider(StaticCredential::new(api_key))
+    }
+
+    pub fn with_api_key_provider(mut self, provider: impl CredentialProvider + 'static) -> Self {
+        self.auth = Some(AzureAuth::ApiKey(Arc::new(provider)));
+        self
+    }
+
+    /// Authenticate with Microsoft Entra ID tokens from `provider`, which
+    /// should cache tokens until they are close to expiry.
+    pub fn with_token_provider(mut self, provider: impl CredentialProvider + 'static) -> Self {
+        self.auth = Some(AzureAuth::BearerToken(Arc::new(provider)));
+        self
+    }
+
+    /// Deployment serving `model`.
+    pub fn deployment_for<'a>(&'a self, model: Option<&'a str>) -> Option<&'a str> {
+        model
+            .and_then(|model| self.deployments.get(model))
+            .or(self.default_deployment.as_ref())
+            .map(String::as_str)
+            .or(model)
+    }
+
+    /// Path below `{endpoint}/openai/` for an OpenAI API path.
+    pub(crate) fn path(&self, path: &str, model: Option<&str>) -> String {
+        let path = path.trim_start_matches('/');
+        let route = path.split('?').next().unwrap_or_default();
+        if Self::is_deployment_scoped(route) {
+            if let Some(deployment) = self.deployment_for(model) {
+                return format!("deployments/{deployment}/{path}");
+            }
+        }
+        path.to_string()
+    }
+
+    fn is_deployment_scoped(route: &str) -> bool {
+        matches!(
+            route,
+            "chat/completions"
+                | "completions"
+                | "embeddings"
+                | "images/generations"
+                | "images/edits"
+                | "images/variations"
+                | "audio/transcriptions"
+                | "audio/translations"
+                | "audio/speech"
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deployment_paths() {
+        let config = AzureConfig::from_resource("contoso", "2024-10-21")
+            .with_deployment("gpt-4o", "prod-gpt4o");
+        assert_eq!(config.endpoint, "https://contoso.openai.azure.com");
+        assert_eq!(
+            config.path("chat/completions", Some("gpt-4o")),
+            "deployments/prod-gpt4o/chat/completions"
+        );
+        assert_eq!(
+            config.path("embeddings", Some("text-embedding-3-small")),
+            "deployments/text-embedding-3-small/embeddings"
+        );
+        assert_eq!(config.path("files/file-1", None), "files/file-1");
+        assert_eq!(
+            config.path("batches?limit=10", None),
+            "batches?limit=10".to_string()
+        );
+    }
+
+    #[test]
+    fn test_default_deployment() {
+        let config = AzureConfig::new("https://contoso.openai.azure.com", "2024-10-21")
+            .with_default_deployment("fallback");
+        assert_eq!(config.deployment_for(Some("gpt-4o")), Some("fallback"));
+        assert_eq!(config.deployment_for(None), Some("fallback"));
+        assert_eq!(
+            config.path("audio/transcriptions", None),
+            "deployments/fallback/audio/transcriptions"
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::v1::pagination::{CursorPage, PageItem};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -60,13 +61,12 @@ pub struct BatchResponse {
     pub status: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ListBatchResponse {
-    pub object: String,
-    pub data: Vec<BatchResponse>,
-    pub first_id: String,
-    pub last_id: String,
-    pub has_more: bool,
+pub type ListBatchResponse = CursorPage<BatchResponse>;
+
+impl PageItem for BatchResponse {
+    fn page_id(&self) -> &str {
+        &self.id
+    }
 }
 
 impl CreateBatchRequest {
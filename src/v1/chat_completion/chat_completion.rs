@@ -1,5 +1,7 @@
+use crate::v1::chat_completion::chat_completion_stream::StreamOptions;
 use crate::v1::chat_completion::{
-    ChatCompletionChoice, Reasoning, ReasoningEffort, Tool, ToolChoiceType,
+    AudioOutputConfig, ChatCompletionChoice, Modality, Prediction, Reasoning, ReasoningEffort,
+    ResponseFormat, ServiceTier, Tool, ToolChoiceType, Verbosity, WebSearchOptions,
 };
 use crate::v1::common;
 use crate::{
@@ -9,7 +11,7 @@ use crate::{
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ChatCompletionRequest {
@@ -22,7 +24,7 @@ pub struct ChatCompletionRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub n: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub response_format: Option<Value>,
+    pub response_format: Option<ResponseFormat>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stop: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -55,6 +57,42 @@ pub struct ChatCompletionRequest {
     /// preprocessing steps. When None, no transforms are applied.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transforms: Option<Vec<String>>,
+    /// Upper bound on generated tokens, including reasoning tokens. Replaces
+    /// `max_tokens`, which reasoning models reject.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_completion_tokens: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logprobs: Option<bool>,
+    /// Number of most likely tokens to return per position, 0 to 20.
+    /// Requires `logprobs`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_logprobs: Option<i64>,
+    /// Keep the completion for the stored completions endpoints and evals.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub store: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modalities: Option<Vec<Modality>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio: Option<AudioOutputConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prediction: Option<Prediction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_tier: Option<ServiceTier>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub web_search_options: Option<WebSearchOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt_cache_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verbosity: Option<Verbosity>,
+    /// Only used when streaming.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_options: Option<StreamOptions>,
+    /// Fields this crate does not model, e.g. provider-specific options,
+    /// sent as top-level keys of the request body.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, Value>,
 }
 
 impl ChatCompletionRequest {
@@ -79,6 +117,20 @@ impl ChatCompletionRequest {
             reasoning: None,
             reasoning_effort: None,
             transforms: None,
+            max_completion_tokens: None,
+            logprobs: None,
+            top_logprobs: None,
+            store: None,
+            metadata: None,
+            modalities: None,
+            audio: None,
+            prediction: None,
+            service_tier: None,
+            web_search_options: None,
+            prompt_cache_key: None,
+            verbosity: None,
+            stream_options: None,
+            extra: BTreeMap::new(),
         }
     }
 }
@@ -88,7 +140,7 @@ impl_builder_methods!(
     temperature: f64,
     top_p: f64,
     n: i64,
-    response_format: Value,
+    response_format: ResponseFormat,
     stop: Vec<String>,
     max_tokens: i64,
     presence_penalty: f64,
@@ -101,9 +153,30 @@ impl_builder_methods!(
     tool_choice: ToolChoiceType,
     reasoning: Reasoning,
     reasoning_effort: ReasoningEffort,
-    transforms: Vec<String>
+    transforms: Vec<String>,
+    max_completion_tokens: i64,
+    logprobs: bool,
+    top_logprobs: i64,
+    store: bool,
+    metadata: HashMap<String, String>,
+    modalities: Vec<Modality>,
+    audio: AudioOutputConfig,
+    prediction: Prediction,
+    service_tier: ServiceTier,
+    web_search_options: WebSearchOptions,
+    prompt_cache_key: String,
+    verbosity: Verbosity,
+    stream_options: StreamOptions
 );
 
+impl ChatCompletionRequest {
+    /// Sets a field this crate does not model.
+    pub fn extra(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.extra.insert(key.into(), value.into());
+        self
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ChatCompletionResponse {
     pub id: Option<String>,
@@ -111,13 +184,22 @@ pub struct ChatCompletionResponse {
     pub created: i64,
     pub model: String,
     pub choices: Vec<ChatCompletionChoice>,
+    #[serde(default)]
     pub usage: common::Usage,
     pub system_fingerprint: Option<String>,
+    /// The tier that actually served the request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service_tier: Option<ServiceTier>,
+    /// Set on stored completions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::v1::chat_completion::{ReasoningEffort, ReasoningSummary};
+    use crate::v1::chat_completion::{
+        ChatCompletionMessage, FinishReason, ReasoningEffort, ReasoningSummary,
+    };
 
     use super::*;
     use serde_json::json;
@@ -246,6 +328,193 @@ mod tests {
         assert_eq!(req.reasoning_effort, Some(ReasoningEffort::Minimal));
     }
 
+    #[test]
+    fn test_full_request_roundtrip_with_extra() {
+        let req = ChatCompletionRequest::new("gpt-4o".to_string(), vec![])
+            .max_completion_tokens(256)
+            .logprobs(true)
+            .top_logprobs(2)
+            .store(true)
+            .metadata(HashMap::from([("team".to_string(), "eval".to_string())]))
+            .modalities(vec![Modality::Text, Modality::Audio])
+            .audio(AudioOutputConfig::new(
+                "alloy",
+                crate::v1::chat_completion::AudioOutputFormat::Wav,
+            ))
+            .prediction(Prediction::content("fn main() {}"))
+            .service_tier(ServiceTier::Flex)
+            .web_search_options(WebSearchOptions {
+                search_context_size: Some(crate::v1::chat_completion::SearchContextSize::Low),
+                user_location: None,
+            })
+            .prompt_cache_key("user-1".to_string())
+            .verbosity(Verbosity::Low)
+            .extra("provider", json!({"order": ["openai"]}));
+
+        let value = serde_json::to_value(&req).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "model": "gpt-4o",
+                "messages": [],
+                "max_completion_tokens": 256,
+                "logprobs": true,
+                "top_logprobs": 2,
+                "store": true,
+                "metadata": {"team": "eval"},
+                "modalities": ["text", "audio"],
+                "audio": {"voice": "alloy", "format": "wav"},
+                "prediction": {"type": "content", "content": "fn main() {}"},
+                "service_tier": "flex",
+                "web_search_options": {"search_context_size": "low"},
+                "prompt_cache_key": "user-1",
+                "verbosity": "low",
+                "provider": {"order": ["openai"]}
+            })
+        );
+
+        let parsed: ChatCompletionRequest = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(parsed.extra["provider"], json!({"order": ["openai"]}));
+        assert_eq!(parsed.service_tier, Some(ServiceTier::Flex));
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), value);
+    }
+
+    #[test]
+    fn test_response_with_logprobs_and_usage_details() {
+        let res: ChatCompletionResponse = serde_json::from_value(json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 1,
+            "model": "gpt-4o",
+            "service_tier": "default",
+            "choices": [{
+                "index": 0,
+                "finish_reason": "function_call",
+                "message": {"role": "assistant", "content": null, "refusal": "No."},
+                "logprobs": {
+                    "content": null,
+                    "refusal": [{"token": "No", "logprob": -0.1, "bytes": [78, 111],
+                                 "top_logprobs": [{"token": "No", "logprob": -0.1, "bytes": null}]}]
+                }
+            }],
+            "usage": {
+                "prompt_tokens": 20,
+                "completion_tokens": 10,
+                "total_tokens": 30,
+                "prompt_tokens_details": {"cached_tokens": 16, "audio_tokens": 0},
+                "completion_tokens_details": {"reasoning_tokens": 4}
+            }
+        }))
+        .unwrap();
+
+        let choice = &res.choices[0];
+        assert_eq!(choice.finish_reason, Some(FinishReason::function_call));
+        assert_eq!(choice.message.refusal.as_deref(), Some("No."));
+        let refusal = choice.logprobs.as_ref().unwrap().refusal.as_ref().unwrap();
+        assert_eq!(refusal[0].bytes, Some(vec![78, 111]));
+        assert_eq!(refusal[0].top_logprobs.len(), 1);
+        assert_eq!(res.service_tier, Some(ServiceTier::Default));
+        let tier: ServiceTier = serde_json::from_str(r#""on_demand""#).unwrap();
+        assert_eq!(tier, ServiceTier::Other("on_demand".to_string()));
+        assert_eq!(serde_json::to_string(&tier).unwrap(), r#""on_demand""#);
+        assert_eq!(res.usage.prompt_tokens_details.unwrap().cached_tokens, 16);
+        let completion = res.usage.completion_tokens_details.unwrap();
+        assert_eq!(completion.reasoning_tokens, 4);
+        assert_eq!(completion.rejected_prediction_tokens, 0);
+    }
+
+    #[test]
+    fn test_multimodal_content_parts_roundtrip() {
+        use crate::v1::chat_completion::{
+            Content, ContentPart, ImageDetail, InputAudioFormat, MessageRole,
+        };
+
+        let png = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+        let message = ChatCompletionMessage {
+            role: MessageRole::user,
+            content: Content::Parts(vec![
+                ContentPart::text("Describe these."),
+                ContentPart::image_bytes(&png)
+                    .unwrap()
+                    .with_detail(ImageDetail::High),
+                ContentPart::input_audio(b"RIFF", InputAudioFormat::Wav),
+                ContentPart::file_bytes("report.pdf", b"%PDF-1.7"),
+                ContentPart::file_id("file-1"),
+            ]),
+            name: None,
+            tool_calls: None,
+            tool_call_id: None,
+            audio: None,
+        };
+
+        let value = serde_json::to_value(&message).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "role": "user",
+                "content": [
+                    {"type": "text", "text": "Describe these."},
+                    {"type": "image_url",
+                     "image_url": {"url": "data:image/png;base64,iVBORw0KGgo=", "detail": "high"}},
+                    {"type": "input_audio", "input_audio": {"data": "UklGRg==", "format": "wav"}},
+                    {"type": "file",
+                     "file": {"file_data": "data:application/pdf;base64,JVBERi0xLjc=",
+                              "filename": "report.pdf"}},
+                    {"type": "file", "file": {"file_id": "file-1"}}
+                ]
+            })
+        );
+
+        let parsed: ChatCompletionMessage = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed.content, message.content);
+        assert!(ContentPart::image_bytes(b"%PDF-1.7").is_err());
+    }
+
+    #[test]
+    fn test_audio_response_and_follow_up_reference() {
+        use crate::v1::chat_completion::{Content, MessageRole};
+
+        let res: ChatCompletionResponse = serde_json::from_value(json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 1,
+            "model": "gpt-audio",
+            "choices": [{
+                "index": 0,
+                "finish_reason": "stop",
+                "message": {
+                    "role": "assistant",
+                    "content": null,
+                    "audio": {"id": "audio_1", "data": "UklGRg==", "expires_at": 1700000000,
+                              "transcript": "Hello!"}
+                }
+            }],
+            "usage": {"prompt_tokens": 1, "completion_tokens": 1, "total_tokens": 2}
+        }))
+        .unwrap();
+
+        let audio = res.choices[0].message.audio.as_ref().unwrap();
+        assert_eq!(audio.data, b"RIFF");
+        assert_eq!(audio.transcript, "Hello!");
+        assert_eq!(
+            serde_json::to_value(audio).unwrap()["data"],
+            json!("UklGRg==")
+        );
+
+        let follow_up = ChatCompletionMessage {
+            role: MessageRole::assistant,
+            content: Content::Text(String::new()),
+            name: None,
+            tool_calls: None,
+            tool_call_id: None,
+            audio: Some(audio.reference()),
+        };
+        assert_eq!(
+            serde_json::to_value(&follow_up).unwrap(),
+            json!({"role": "assistant", "content": null, "audio": {"id": "audio_1"}})
+        );
+    }
+
     #[test]
     fn test_openrouter_reasoning_response_deserialization() {
         let json_str = r#"{
@@ -0,0 +1,267 @@
+use crate::v1::chat_completion::chat_completion::ChatCompletionResponse;
+use crate::v1::chat_completion::chat_completion_stream::{ChatCompletionChunk, ToolCallDelta};
+use crate::v1::chat_completion::{
+    ChatCompletionAudio, ChatCompletionChoice, ChatCompletionMessageForResponse, ChoiceLogprobs,
+    FinishReason, MessageRole, ServiceTier, ToolCall, ToolCallFunction,
+};
+use crate::v1::common::Usage;
+use std::collections::BTreeMap;
+
+/// Reassembles a `ChatCompletionResponse` from streamed chunks.
+///
+/// Deltas are merged per choice index, and tool call fragments per tool call
+/// index, so the argument JSON is complete once the stream ends. `snapshot`
+/// can be called after any chunk to see the response so far.
+///
+/// ```no_run
+/// # use openai_api_rs::v1::api::OpenAIClient;
+/// # use openai_api_rs::v1::chat_completion::chat_completion_accumulator::ChatCompletionAccumulator;
+/// # use openai_api_rs::v1::chat_completion::chat_completion_stream::ChatCompletionStreamRequest;
+/// # use futures_util::StreamExt;
+/// # async fn run(client: OpenAIClient, req: ChatCompletionStreamRequest) -> Result<(), Box<dyn std::error::Error>> {
+/// let mut stream = client.chat_completion_chunk_stream(req).await?;
+/// let mut accumulator = ChatCompletionAccumulator::new();
+/// while let Some(chunk) = stream.next().await {
+///     accumulator.push(&chunk?);
+/// }
+/// let response = accumulator.finish();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ChatCompletionAccumulator {
+    id: Option<String>,
+    object: Option<String>,
+    created: i64,
+    model: String,
+    system_fingerprint: Option<String>,
+    service_tier: Option<ServiceTier>,
+    usage: Option<Usage>,
+    choices: BTreeMap<i64, ChoiceState>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct ChoiceState {
+    role: Option<MessageRole>,
+    content: Option<String>,
+    refusal: Option<String>,
+    reasoning: Option<String>,
+    tool_calls: BTreeMap<i64, ToolCall>,
+    logprobs: Option<ChoiceLogprobs>,
+    audio: Option<ChatCompletionAudio>,
+    finish_reason: Option<FinishReason>,
+}
+
+impl ChatCompletionAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Merges one chunk into the response.
+    pub fn push(&mut self, chunk: &ChatCompletionChunk) {
+        if !chunk.id.is_empty() {
+            self.id = Some(chunk.id.clone());
+        }
+        self.object = Some("chat.completion".to_string());
+        if chunk.created != 0 {
+            self.created = chunk.created;
+        }
+        if !chunk.model.is_empty() {
+            self.model = chunk.model.clone();
+        }
+        if chunk.system_fingerprint.is_some() {
+            self.system_fingerprint = chunk.system_fingerprint.clone();
+        }
+        if chunk.service_tier.is_some() {
+            self.service_tier = chunk.service_tier.clone();
+        }
+        if chunk.usage.is_some() {
+            self.usage = chunk.usage.clone();
+        }
+
+        for choice in &chunk.choices {
+            let state = self.choices.entry(choice.index).or_default();
+            let delta = &choice.delta;
+            if delta.role.is_some() {
+                state.role = delta.role.clone();
+            }
+            append(&mut state.content, delta.content.as_deref());
+            append(&mut state.refusal, delta.refusal.as_deref());
+            append(&mut state.reasoning, delta.reasoning.as_deref());
+            for fragment in delta.tool_calls.iter().flatten() {
+                merge_tool_call(&mut state.tool_calls, fragment);
+            }
+            if let Some(fragment) = &delta.audio {
+                let audio = state.audio.get_or_insert_with(Default::default);
+                if let Some(id) = &fragment.id {
+                    audio.id = id.clone();
+                }
+                if let Some(expires_at) = fragment.expires_at {
+                    audio.expires_at = expires_at;
+                }
+                if let Some(data) = &fragment.data {
+                    audio.data.extend_from_slice(data);
+                }
+                if let Some(transcript) = &fragment.transcript {
+                    audio.transcript.push_str(transcript);
+                }
+            }
+            if let Some(logprobs) = &choice.logprobs {
+                let merged = state.logprobs.get_or_insert_with(Default::default);
+                for (target, tokens) in [
+                    (&mut merged.content, &logprobs.content),
+                    (&mut merged.refusal, &logprobs.refusal),
+                ] {
+                    if let Some(tokens) = tokens {
+                        target
+                            .get_or_insert_with(Vec::new)
+                            .extend(tokens.iter().cloned());
+                    }
+                }
+            }
+            if choice.finish_reason.is_some() {
+                state.finish_reason = choice.finish_reason.clone();
+            }
+        }
+    }
+
+    /// Usage reported by the server, if `include_usage` was requested and
+    /// the final chunk has arrived.
+    pub fn usage(&self) -> Option<&Usage> {
+        self.usage.as_ref()
+    }
+
+    /// The response assembled from the chunks pushed so far. Usage is zero
+    /// until the server reports it.
+    pub fn snapshot(&self) -> ChatCompletionResponse {
+        ChatCompletionResponse {
+            id: self.id.clone(),
+            object: self.object.clone(),
+            created: self.created,
+            model: self.model.clone(),
+            choices: self
+                .choices
+                .iter()
+                .map(|(&index, state)| ChatCompletionChoice {
+                    index,
+                    message: ChatCompletionMessageForResponse {
+                        role: state.role.clone().unwrap_or(MessageRole::assistant),
+                        content: state.content.clone(),
+                        refusal: state.refusal.clone(),
+                        reasoning_content: state.reasoning.clone(),
+                        name: None,
+                        tool_calls: (!state.tool_calls.is_empty())
+                            .then(|| state.tool_calls.values().cloned().collect()),
+                        audio: state.audio.clone(),
+                    },
+                    logprobs: state.logprobs.clone(),
+                    finish_reason: state.finish_reason.clone(),
+                    finish_details: None,
+                })
+                .collect(),
+            usage: self.usage.clone().unwrap_or_default(),
+            system_fingerprint: self.system_fingerprint.clone(),
+            service_tier: self.service_tier.clone(),
+            metadata: None,
+        }
+    }
+
+    /// The complete response, once the stream has ended.
+    pub fn finish(self) -> ChatCompletionResponse {
+        self.snapshot()
+    }
+}
+
+/// Merges a tool call fragment into the calls seen so far, keyed by index.
+pub(crate) fn merge_tool_call(calls: &mut BTreeMap<i64, ToolCall>, fragment: &ToolCallDelta) {
+    let call = calls.entry(fragment.index).or_insert_with(|| ToolCall {
+        id: String::new(),
+        r#type: "function".to_string(),
+        function: ToolCallFunction {
+            name: None,
+            arguments: None,
+        },
+    });
+    if let Some(id) = &fragment.id {
+        call.id = id.clone();
+    }
+    if let Some(r#type) = &fragment.r#type {
+        call.r#type = r#type.clone();
+    }
+    if let Some(function) = &fragment.function {
+        append(&mut call.function.name, function.name.as_deref());
+        append(&mut call.function.arguments, function.arguments.as_deref());
+    }
+}
+
+fn append(target: &mut Option<String>, fragment: Option<&str>) {
+    if let Some(fragment) = fragment {
+        target.get_or_insert_with(String::new).push_str(fragment);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn chunk(value: serde_json::Value) -> ChatCompletionChunk {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_accumulates_content_and_tool_calls() {
+        let chunks = [
+            json!({"id": "c1", "object": "chat.completion.chunk", "created": 1, "model": "gpt-4o",
+                "choices": [{"index": 0, "delta": {"role": "assistant", "reasoning": "Think"}},
+                            {"index": 1, "delta": {"role": "assistant", "content": "Hel"}}]}),
+            json!({"id": "c1", "choices": [
+            {"index": 1, "delta": {"content": "lo"}, "finish_reason": "stop"},
+            {"index": 0, "delta": {"reasoning": "ing", "tool_calls": [
+                {"index": 0, "id": "call_a", "type": "function",
+                 "function": {"name": "get_weather", "arguments": "{\"ci"}},
+                {"index": 1, "id": "call_b", "function": {"name": "get_time", "arguments": ""}}
+            ]}}]}),
+            json!({"id": "c1", "choices": [{"index": 0, "delta": {"tool_calls": [
+                {"index": 1, "function": {"arguments": "{}"}},
+                {"index": 0, "function": {"arguments": "ty\":\"Tokyo\"}"}}
+            ]}, "finish_reason": "tool_calls"}]}),
+            json!({"id": "c1", "choices": [],
+                "usage": {"prompt_tokens": 5, "completion_tokens": 7, "total_tokens": 12}}),
+        ];
+
+        let mut accumulator = ChatCompletionAccumulator::new();
+        accumulator.push(&chunk(chunks[0].clone()));
+        let snapshot = accumulator.snapshot();
+        assert_eq!(snapshot.choices[1].message.content.as_deref(), Some("Hel"));
+        assert_eq!(snapshot.usage.total_tokens, 0);
+
+        for value in &chunks[1..] {
+            accumulator.push(&chunk(value.clone()));
+        }
+        let response = accumulator.finish();
+
+        assert_eq!(response.id.as_deref(), Some("c1"));
+        assert_eq!(response.model, "gpt-4o");
+        assert_eq!(response.usage.total_tokens, 12);
+        assert_eq!(response.choices.len(), 2);
+
+        let first = &response.choices[0];
+        assert_eq!(first.message.reasoning_content.as_deref(), Some("Thinking"));
+        assert_eq!(first.finish_reason, Some(FinishReason::tool_calls));
+        let calls = first.message.tool_calls.as_ref().unwrap();
+        assert_eq!(calls[0].id, "call_a");
+        assert_eq!(calls[0].function.name.as_deref(), Some("get_weather"));
+        assert_eq!(
+            calls[0].function.arguments.as_deref(),
+            Some("{\"city\":\"Tokyo\"}")
+        );
+        assert_eq!(calls[1].r#type, "function");
+        assert_eq!(calls[1].function.arguments.as_deref(), Some("{}"));
+
+        let second = &response.choices[1];
+        assert_eq!(second.message.content.as_deref(), Some("Hello"));
+        assert_eq!(second.finish_reason, Some(FinishReason::stop));
+        assert!(second.message.tool_calls.is_none());
+    }
+}
@@ -0,0 +1,121 @@
+//! Types for chat completions kept server-side with `store: true`.
+
+use crate::v1::chat_completion::chat_completion::ChatCompletionResponse;
+use crate::v1::chat_completion::ChatCompletionMessageForResponse;
+use crate::v1::pagination::{CursorPage, PageItem};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+pub type ChatCompletionList = CursorPage<ChatCompletionResponse>;
+
+pub type ChatCompletionMessageList = CursorPage<StoredChatCompletionMessage>;
+
+impl PageItem for ChatCompletionResponse {
+    fn page_id(&self) -> &str {
+        self.id.as_deref().unwrap_or_default()
+    }
+}
+
+/// Filters for listing stored chat completions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChatCompletionListFilter {
+    pub model: Option<String>,
+    /// Only completions whose metadata has all of these pairs.
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl ChatCompletionListFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// The list path with the filters as a query string.
+    pub(crate) fn to_path(&self, path: &str) -> String {
+        let mut params = url::form_urlencoded::Serializer::new(String::new());
+        if let Some(model) = &self.model {
+            params.append_pair("model", model);
+        }
+        for (key, value) in &self.metadata {
+            params.append_pair(&format!("metadata[{key}]"), value);
+        }
+        let query = params.finish();
+        if query.is_empty() {
+            path.to_string()
+        } else {
+            format!("{path}?{query}")
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct UpdateChatCompletionRequest {
+    /// Replaces the stored metadata.
+    pub metadata: HashMap<String, String>,
+}
+
+impl UpdateChatCompletionRequest {
+    pub fn new(metadata: HashMap<String, String>) -> Self {
+        Self { metadata }
+    }
+}
+
+/// A message of the request that produced a stored chat completion.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct StoredChatCompletionMessage {
+    pub id: String,
+    #[serde(flatten)]
+    pub message: ChatCompletionMessageForResponse,
+}
+
+impl PageItem for StoredChatCompletionMessage {
+    fn page_id(&self) -> &str {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v1::chat_completion::MessageRole;
+    use crate::v1::pagination::ListParams;
+    use serde_json::json;
+
+    #[test]
+    fn test_filter_path_and_message_list() {
+        let filter = ChatCompletionListFilter::new()
+            .with_model("gpt-4o")
+            .with_metadata("team", "eval");
+        let path = ListParams::new()
+            .with_limit(2)
+            .to_path(&filter.to_path("chat/completions"));
+        assert_eq!(
+            path,
+            "chat/completions?model=gpt-4o&metadata%5Bteam%5D=eval&limit=2"
+        );
+
+        let list: ChatCompletionMessageList = serde_json::from_value(json!({
+            "object": "list",
+            "data": [{"id": "msg_0", "role": "developer", "content": "Be brief."},
+                     {"id": "msg_1", "role": "user", "content": "Hi", "name": null,
+                      "content_parts": null}],
+            "first_id": "msg_0",
+            "last_id": "msg_1",
+            "has_more": false
+        }))
+        .unwrap();
+        assert_eq!(list.data[0].message.role, MessageRole::developer);
+        assert_eq!(list.data[1].page_id(), "msg_1");
+        assert_eq!(list.data[1].message.role, MessageRole::user);
+        assert_eq!(list.data[1].message.content.as_deref(), Some("Hi"));
+    }
+}
@@ -1,13 +1,25 @@
-use crate::v1::chat_completion::{Reasoning, ReasoningEffort, Tool, ToolCall, ToolChoiceType};
+use crate::v1::api::ByteStream;
+use crate::v1::chat_completion::chat_completion::ChatCompletionResponse;
+use crate::v1::chat_completion::chat_completion_accumulator::{
+    merge_tool_call, ChatCompletionAccumulator,
+};
+use crate::v1::chat_completion::{
+    base64_data, AudioOutputConfig, ChoiceLogprobs, FinishReason, MessageRole, Modality,
+    Prediction, Reasoning, ReasoningEffort, ResponseFormat, ServiceTier, Tool, ToolCall,
+    ToolChoiceType, Verbosity, WebSearchOptions,
+};
+use crate::v1::common::Usage;
+use crate::v1::error::APIError;
+use crate::v1::sse::{SseEvent, SseStream};
 use crate::{
     impl_builder_methods,
     v1::chat_completion::{serialize_tool_choice, ChatCompletionMessage},
 };
 
-use futures_util::Stream;
+use futures_util::{Stream, StreamExt};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
@@ -22,7 +34,7 @@ pub struct ChatCompletionStreamRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub n: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub response_format: Option<Value>,
+    pub response_format: Option<ResponseFormat>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stop: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -55,6 +67,49 @@ pub struct ChatCompletionStreamRequest {
     /// preprocessing steps. When None, no transforms are applied.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transforms: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_options: Option<StreamOptions>,
+    /// Upper bound on generated tokens, including reasoning tokens. Replaces
+    /// `max_tokens`, which reasoning models reject.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_completion_tokens: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logprobs: Option<bool>,
+    /// Number of most likely tokens to return per position, 0 to 20.
+    /// Requires `logprobs`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_logprobs: Option<i64>,
+    /// Keep the completion for the stored completions endpoints and evals.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub store: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modalities: Option<Vec<Modality>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio: Option<AudioOutputConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prediction: Option<Prediction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_tier: Option<ServiceTier>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub web_search_options: Option<WebSearchOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt_cache_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verbosity: Option<Verbosity>,
+    /// Fields this crate does not model, e.g. provider-specific options,
+    /// sent as top-level keys of the request body.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct StreamOptions {
+    /// Send a final chunk with token usage for the whole request. Its
+    /// `choices` is empty.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_usage: Option<bool>,
 }
 
 impl ChatCompletionStreamRequest {
@@ -79,6 +134,20 @@ impl ChatCompletionStreamRequest {
             reasoning: None,
             reasoning_effort: None,
             transforms: None,
+            stream_options: None,
+            max_completion_tokens: None,
+            logprobs: None,
+            top_logprobs: None,
+            store: None,
+            metadata: None,
+            modalities: None,
+            audio: None,
+            prediction: None,
+            service_tier: None,
+            web_search_options: None,
+            prompt_cache_key: None,
+            verbosity: None,
+            extra: BTreeMap::new(),
         }
     }
 }
@@ -88,7 +157,7 @@ impl_builder_methods!(
     temperature: f64,
     top_p: f64,
     n: i64,
-    response_format: Value,
+    response_format: ResponseFormat,
     stop: Vec<String>,
     max_tokens: i64,
     presence_penalty: f64,
@@ -101,151 +170,403 @@ impl_builder_methods!(
     tool_choice: ToolChoiceType,
     reasoning: Reasoning,
     reasoning_effort: ReasoningEffort,
-    transforms: Vec<String>
+    transforms: Vec<String>,
+    stream_options: StreamOptions,
+    max_completion_tokens: i64,
+    logprobs: bool,
+    top_logprobs: i64,
+    store: bool,
+    metadata: HashMap<String, String>,
+    modalities: Vec<Modality>,
+    audio: AudioOutputConfig,
+    prediction: Prediction,
+    service_tier: ServiceTier,
+    web_search_options: WebSearchOptions,
+    prompt_cache_key: String,
+    verbosity: Verbosity
 );
 
+impl ChatCompletionStreamRequest {
+    /// Sets a field this crate does not model.
+    pub fn extra(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.extra.insert(key.into(), value.into());
+        self
+    }
+}
+
+/// One `chat.completion.chunk` object of a streamed chat completion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatCompletionChunk {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub object: String,
+    #[serde(default)]
+    pub created: i64,
+    #[serde(default)]
+    pub model: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_fingerprint: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service_tier: Option<ServiceTier>,
+    #[serde(default)]
+    pub choices: Vec<ChatCompletionChunkChoice>,
+    /// Only set on the final chunk, when `stream_options.include_usage` is
+    /// enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<Usage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatCompletionChunkChoice {
+    pub index: i64,
+    #[serde(default)]
+    pub delta: ChatCompletionChunkDelta,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logprobs: Option<ChoiceLogprobs>,
+    #[serde(default)]
+    pub finish_reason: Option<FinishReason>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChatCompletionChunkDelta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<MessageRole>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refusal: Option<String>,
+    #[serde(
+        default,
+        alias = "reasoning_content",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub reasoning: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<ToolCallDelta>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio: Option<AudioDelta>,
+}
+
+/// A fragment of a spoken answer. `id` and `expires_at` arrive once; `data`
+/// and `transcript` are appended in order.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AudioDelta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Decoded audio bytes of this fragment.
+    #[serde(
+        default,
+        with = "base64_data::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub data: Option<Vec<u8>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transcript: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<i64>,
+}
+
+/// A fragment of a tool call. Fragments with the same `index` make up one
+/// call; `id`, `type` and the function name arrive only with the first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolCallDelta {
+    pub index: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<FunctionCallDelta>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FunctionCallDelta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub enum ChatCompletionStreamResponse {
     Content(String),
     Reasoning(String),
     ToolCall(Vec<ToolCall>),
+    Audio(AudioDelta),
+    Done,
+}
+
+enum ChatStreamEvent {
     Done,
+    Data(Value),
 }
 
-pub struct ChatCompletionStream<S: Stream<Item = Result<bytes::Bytes, reqwest::Error>> + Unpin> {
-    pub response: S,
-    pub buffer: String,
-    pub first_chunk: bool,
+/// Reads one event of a chat completion stream; `None` for events that
+/// carry nothing.
+fn read_event(event: SseEvent) -> Option<Result<ChatStreamEvent, APIError>> {
+    if event.data.is_empty() {
+        return None;
+    }
+
+    if event.data == "[DONE]" {
+        return Some(Ok(ChatStreamEvent::Done));
+    }
+
+    let json = match serde_json::from_str::<Value>(&event.data) {
+        Ok(json) => json,
+        Err(source) => {
+            return Some(Err(APIError::DeserializeError {
+                source,
+                body: event.data,
+            }))
+        }
+    };
+
+    if let Some(error) =
+        json.get("error")
+            .filter(|error| !error.is_null())
+            .or((event.event.as_deref() == Some("error")).then_some(&json))
+    {
+        return Some(Err(APIError::StreamError {
+            message: error
+                .get("message")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .unwrap_or_else(|| error.to_string()),
+        }));
+    }
+
+    Some(Ok(ChatStreamEvent::Data(json)))
+}
+
+/// Server-sent events of a streamed chat completion.
+///
+/// Yields an error for transport failures, unparsable chunks and `error`
+/// payloads sent by the server. A stream that ends without
+/// `ChatCompletionStreamResponse::Done` was cut short; `is_done` tells the
+/// two apart after the stream finishes.
+///
+/// Tool call fragments are merged by index and yielded as one
+/// `ChatCompletionStreamResponse::ToolCall` with complete arguments once the
+/// choice finishes.
+pub struct ChatCompletionStream<S = ByteStream>
+where
+    S: Stream<Item = Result<bytes::Bytes, APIError>> + Unpin,
+{
+    events: SseStream<S>,
+    done: bool,
+    tool_calls: BTreeMap<i64, ToolCall>,
+    /// Responses parsed from one event beyond the first, yielded next.
+    pending: VecDeque<ChatCompletionStreamResponse>,
 }
 
 impl<S> ChatCompletionStream<S>
 where
-    S: Stream<Item = Result<bytes::Bytes, reqwest::Error>> + Unpin,
+    S: Stream<Item = Result<bytes::Bytes, APIError>> + Unpin,
 {
-    fn find_event_delimiter(buffer: &str) -> Option<(usize, usize)> {
-        let carriage_idx = buffer.find("\r\n\r\n");
-        let newline_idx = buffer.find("\n\n");
-
-        match (carriage_idx, newline_idx) {
-            (Some(r_idx), Some(n_idx)) => {
-                if r_idx <= n_idx {
-                    Some((r_idx, 4))
-                } else {
-                    Some((n_idx, 2))
+    pub fn new(response: S) -> Self {
+        Self {
+            events: SseStream::new(response),
+            done: false,
+            tool_calls: BTreeMap::new(),
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Whether the server sent the final `[DONE]` message.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    fn take_tool_calls(&mut self) -> ChatCompletionStreamResponse {
+        ChatCompletionStreamResponse::ToolCall(
+            std::mem::take(&mut self.tool_calls).into_values().collect(),
+        )
+    }
+
+    /// Interprets one event; `None` for events that carry nothing to yield.
+    fn parse_event(
+        &mut self,
+        event: SseEvent,
+    ) -> Option<Result<ChatCompletionStreamResponse, APIError>> {
+        let json = match read_event(event)? {
+            Ok(ChatStreamEvent::Data(json)) => json,
+            Ok(ChatStreamEvent::Done) => {
+                self.done = true;
+                if !self.tool_calls.is_empty() {
+                    self.pending.push_back(ChatCompletionStreamResponse::Done);
+                    return Some(Ok(self.take_tool_calls()));
                 }
+                return Some(Ok(ChatCompletionStreamResponse::Done));
             }
-            (Some(r_idx), None) => Some((r_idx, 4)),
-            (None, Some(n_idx)) => Some((n_idx, 2)),
-            (None, None) => None,
+            Err(error) => return Some(Err(error)),
+        };
+
+        let choice = json.get("choices").and_then(|choices| choices.get(0))?;
+
+        let fragments = choice
+            .pointer("/delta/tool_calls")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|fragment| serde_json::from_value::<ToolCallDelta>(fragment.clone()).ok());
+        for fragment in fragments {
+            merge_tool_call(&mut self.tool_calls, &fragment);
+        }
+        let finished = choice
+            .get("finish_reason")
+            .is_some_and(|reason| !reason.is_null());
+        let delta = choice.get("delta").and_then(parse_delta);
+        if finished && !self.tool_calls.is_empty() {
+            // The finishing chunk may carry a last delta; yield it first.
+            let tool_calls = self.take_tool_calls();
+            return match delta {
+                Some(delta) => {
+                    self.pending.push_back(tool_calls);
+                    Some(delta)
+                }
+                None => Some(Ok(tool_calls)),
+            };
         }
+        delta
+    }
+}
+
+/// The reasoning, audio or content in a chunk's `delta`.
+fn parse_delta(delta: &Value) -> Option<Result<ChatCompletionStreamResponse, APIError>> {
+    if let Some(reasoning) = delta
+        .get("reasoning")
+        .or_else(|| delta.get("reasoning_content"))
+        .and_then(|r| r.as_str())
+    {
+        let output = reasoning.replace("\\n", "\n");
+        return Some(Ok(ChatCompletionStreamResponse::Reasoning(output)));
     }
 
-    fn next_response_from_buffer(&mut self) -> Option<ChatCompletionStreamResponse> {
-        while let Some((idx, delimiter_len)) = Self::find_event_delimiter(&self.buffer) {
-            let event = self.buffer[..idx].to_owned();
-            self.buffer = self.buffer[idx + delimiter_len..].to_owned();
-
-            let mut data_payload = String::new();
-            for line in event.lines() {
-                let trimmed_line = line.trim_end_matches('\r');
-                if let Some(content) = trimmed_line
-                    .strip_prefix("data: ")
-                    .or_else(|| trimmed_line.strip_prefix("data:"))
-                {
-                    if !content.is_empty() {
-                        if !data_payload.is_empty() {
-                            data_payload.push('\n');
-                        }
-                        data_payload.push_str(content);
+    if let Some(audio) = delta.get("audio").filter(|audio| !audio.is_null()) {
+        return Some(
+            serde_json::from_value(audio.clone())
+                .map(ChatCompletionStreamResponse::Audio)
+                .map_err(|source| APIError::DeserializeError {
+                    source,
+                    body: audio.to_string(),
+                }),
+        );
+    }
+
+    if let Some(content) = delta.get("content").and_then(|c| c.as_str()) {
+        let output = content.replace("\\n", "\n");
+        return Some(Ok(ChatCompletionStreamResponse::Content(output)));
+    }
+
+    None
+}
+
+impl<S: Stream<Item = Result<bytes::Bytes, APIError>> + Unpin> Stream for ChatCompletionStream<S> {
+    type Item = Result<ChatCompletionStreamResponse, APIError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Some(response) = self.pending.pop_front() {
+            return Poll::Ready(Some(Ok(response)));
+        }
+        loop {
+            match Pin::new(&mut self.events).poll_next(cx) {
+                Poll::Ready(Some(Ok(event))) => {
+                    if let Some(response) = self.parse_event(event) {
+                        return Poll::Ready(Some(response));
                     }
                 }
+                Poll::Ready(Some(Err(error))) => return Poll::Ready(Some(Err(error))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
             }
+        }
+    }
+}
 
-            if data_payload.is_empty() {
-                continue;
-            }
+/// Typed chunks of a streamed chat completion, with every choice, finish
+/// reason and the final usage.
+///
+/// Errors are reported as for `ChatCompletionStream`. The `[DONE]` message
+/// is not yielded; `is_done` tells whether it arrived.
+pub struct ChatCompletionChunkStream<S = ByteStream>
+where
+    S: Stream<Item = Result<bytes::Bytes, APIError>> + Unpin,
+{
+    events: SseStream<S>,
+    done: bool,
+}
 
-            if data_payload == "[DONE]" {
-                return Some(ChatCompletionStreamResponse::Done);
-            }
+impl<S> ChatCompletionChunkStream<S>
+where
+    S: Stream<Item = Result<bytes::Bytes, APIError>> + Unpin,
+{
+    pub fn new(response: S) -> Self {
+        Self {
+            events: SseStream::new(response),
+            done: false,
+        }
+    }
+
+    /// Whether the server sent the final `[DONE]` message.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Reads the rest of the stream and returns the reassembled response.
+    ///
+    /// Fails with `APIError::StreamError` if the stream ends before
+    /// `[DONE]`; feed a `ChatCompletionAccumulator` directly to keep a
+    /// partial response.
+    pub async fn collect_response(mut self) -> Result<ChatCompletionResponse, APIError> {
+        let mut accumulator = ChatCompletionAccumulator::new();
+        while let Some(chunk) = self.next().await {
+            accumulator.push(&chunk?);
+        }
+        if !self.done {
+            return Err(APIError::StreamError {
+                message: "stream ended before [DONE]".to_string(),
+            });
+        }
+        Ok(accumulator.finish())
+    }
 
-            match serde_json::from_str::<Value>(&data_payload) {
-                Ok(json) => {
-                    if let Some(delta) = json
-                        .get("choices")
-                        .and_then(|choices| choices.get(0))
-                        .and_then(|choice| choice.get("delta"))
-                    {
-                        if let Some(tool_call_response) = delta
-                            .get("tool_calls")
-                            .and_then(|tool_calls| tool_calls.as_array())
-                            .map(|tool_calls_array| {
-                                tool_calls_array
-                                    .iter()
-                                    .filter_map(|v| serde_json::from_value(v.clone()).ok())
-                                    .collect::<Vec<ToolCall>>()
-                            })
-                            .filter(|tool_calls_vec| !tool_calls_vec.is_empty())
-                            .map(ChatCompletionStreamResponse::ToolCall)
-                        {
-                            return Some(tool_call_response);
-                        }
-
-                        if let Some(reasoning) = delta
-                            .get("reasoning")
-                            .or_else(|| delta.get("reasoning_content"))
-                            .and_then(|r| r.as_str())
-                        {
-                            let output = reasoning.replace("\\n", "\n");
-                            return Some(ChatCompletionStreamResponse::Reasoning(output));
-                        }
-
-                        if let Some(content) = delta.get("content").and_then(|c| c.as_str()) {
-                            let output = content.replace("\\n", "\n");
-                            return Some(ChatCompletionStreamResponse::Content(output));
-                        }
+    fn parse_event(&mut self, event: SseEvent) -> Option<Result<ChatCompletionChunk, APIError>> {
+        match read_event(event)? {
+            Ok(ChatStreamEvent::Data(json)) => {
+                Some(serde_json::from_value(json.clone()).map_err(|source| {
+                    APIError::DeserializeError {
+                        source,
+                        body: json.to_string(),
                     }
-                }
-                Err(error) => {
-                    eprintln!("Failed to parse SSE chunk as JSON: {}", error);
-                }
+                }))
             }
+            Ok(ChatStreamEvent::Done) => {
+                self.done = true;
+                None
+            }
+            Err(error) => Some(Err(error)),
         }
-
-        None
     }
 }
 
-impl<S: Stream<Item = Result<bytes::Bytes, reqwest::Error>> + Unpin> Stream
-    for ChatCompletionStream<S>
+impl<S: Stream<Item = Result<bytes::Bytes, APIError>> + Unpin> Stream
+    for ChatCompletionChunkStream<S>
 {
-    type Item = ChatCompletionStreamResponse;
+    type Item = Result<ChatCompletionChunk, APIError>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         loop {
-            if let Some(response) = self.next_response_from_buffer() {
-                return Poll::Ready(Some(response));
-            }
-
-            match Pin::new(&mut self.as_mut().response).poll_next(cx) {
-                Poll::Ready(Some(Ok(chunk))) => {
-                    let chunk_str = String::from_utf8_lossy(&chunk).to_string();
-
-                    if self.first_chunk {
-                        self.first_chunk = false;
+            match Pin::new(&mut self.events).poll_next(cx) {
+                Poll::Ready(Some(Ok(event))) => {
+                    if let Some(chunk) = self.parse_event(event) {
+                        return Poll::Ready(Some(chunk));
                     }
-                    self.buffer.push_str(&chunk_str);
-                }
-                Poll::Ready(Some(Err(error))) => {
-                    eprintln!("Error in stream: {:?}", error);
-                    return Poll::Ready(None);
-                }
-                Poll::Ready(None) => {
-                    return Poll::Ready(None);
-                }
-                Poll::Pending => {
-                    return Poll::Pending;
                 }
+                Poll::Ready(Some(Err(error))) => return Poll::Ready(Some(Err(error))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
             }
         }
     }
@@ -376,20 +697,249 @@ mod tests {
         assert_eq!(req.reasoning_effort, Some(ReasoningEffort::Xhigh));
     }
 
-    #[test]
-    fn test_stream_reasoning_delta() {
-        let mut stream = ChatCompletionStream {
-            response: futures_util::stream::empty(),
-            buffer: "data: {\"choices\":[{\"delta\":{\"reasoning\":\"step 1\"}}]}\n\n".to_string(),
-            first_chunk: false,
-        };
+    #[tokio::test]
+    async fn test_stream_reasoning_delta() {
+        use futures_util::StreamExt;
 
-        let response = stream.next_response_from_buffer();
-        match response {
-            Some(ChatCompletionStreamResponse::Reasoning(reasoning)) => {
+        let chunks: Vec<Result<bytes::Bytes, APIError>> = vec![Ok(
+            "data: {\"choices\":[{\"delta\":{\"reasoning\":\"step 1\"}}]}\n\n".into(),
+        )];
+        let mut stream = ChatCompletionStream::new(futures_util::stream::iter(chunks));
+
+        match stream.next().await {
+            Some(Ok(ChatCompletionStreamResponse::Reasoning(reasoning))) => {
                 assert_eq!(reasoning, "step 1");
             }
             _ => panic!("Expected reasoning delta"),
         }
     }
+
+    #[tokio::test]
+    async fn test_stream_audio_deltas() {
+        use futures_util::StreamExt;
+
+        let body = concat!(
+            "data: {\"id\":\"c1\",\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\",",
+            "\"audio\":{\"id\":\"audio_1\",\"data\":\"AAEC\",\"transcript\":\"Hel\",\"expires_at\":9}}}]}\n\n",
+            "data: {\"id\":\"c1\",\"choices\":[{\"index\":0,\"delta\":",
+            "{\"audio\":{\"data\":\"AwQ=\",\"transcript\":\"lo\"}},\"finish_reason\":\"stop\"}]}\n\n",
+            "data: [DONE]\n\n",
+        );
+        let chunks = || {
+            let chunks: Vec<Result<bytes::Bytes, APIError>> = vec![Ok(body.into())];
+            futures_util::stream::iter(chunks)
+        };
+
+        let mut stream = ChatCompletionStream::new(chunks());
+        match stream.next().await {
+            Some(Ok(ChatCompletionStreamResponse::Audio(audio))) => {
+                assert_eq!(audio.id.as_deref(), Some("audio_1"));
+                assert_eq!(audio.data, Some(vec![0, 1, 2]));
+            }
+            other => panic!("Expected audio delta, got {other:?}"),
+        }
+
+        let response = ChatCompletionChunkStream::new(chunks())
+            .collect_response()
+            .await
+            .unwrap();
+        let audio = response.choices[0].message.audio.as_ref().unwrap();
+        assert_eq!(audio.id, "audio_1");
+        assert_eq!(audio.data, [0, 1, 2, 3, 4]);
+        assert_eq!(audio.transcript, "Hello");
+        assert_eq!(audio.expires_at, 9);
+    }
+
+    #[tokio::test]
+    async fn test_stream_skips_null_audio() {
+        use futures_util::StreamExt;
+
+        let chunks: Vec<Result<bytes::Bytes, APIError>> = vec![Ok(
+            "data: {\"choices\":[{\"delta\":{\"audio\":null,\"content\":\"Hi\"}}]}\n\n".into(),
+        )];
+        let mut stream = ChatCompletionStream::new(futures_util::stream::iter(chunks));
+
+        match stream.next().await {
+            Some(Ok(ChatCompletionStreamResponse::Content(content))) => {
+                assert_eq!(content, "Hi");
+            }
+            other => panic!("Expected content delta, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_ignores_null_error() {
+        use futures_util::StreamExt;
+
+        let chunks: Vec<Result<bytes::Bytes, APIError>> = vec![Ok(
+            "data: {\"error\":null,\"choices\":[{\"delta\":{\"content\":\"Hi\"}}]}\n\n".into(),
+        )];
+        let mut stream = ChatCompletionStream::new(futures_util::stream::iter(chunks));
+
+        match stream.next().await {
+            Some(Ok(ChatCompletionStreamResponse::Content(content))) => {
+                assert_eq!(content, "Hi");
+            }
+            other => panic!("Expected content delta, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_merges_tool_call_fragments() {
+        use futures_util::StreamExt;
+
+        let body = concat!(
+            "data: {\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\",\"tool_calls\":[",
+            "{\"index\":0,\"id\":\"call_1\",\"type\":\"function\",\"function\":{\"name\":\"price\",\"arguments\":\"\"}}]}}]}\n\n",
+            "data: {\"choices\":[{\"index\":0,\"delta\":{\"tool_calls\":[{\"index\":0,\"function\":{\"arguments\":\"{\\\"coin\\\"\"}}]}}]}\n\n",
+            "data: {\"choices\":[{\"index\":0,\"delta\":{\"tool_calls\":[{\"index\":0,\"function\":{\"arguments\":\":\\\"btc\\\"}\"}}]}}]}\n\n",
+            "data: {\"choices\":[{\"index\":0,\"delta\":{},\"finish_reason\":\"tool_calls\"}]}\n\n",
+            "data: [DONE]\n\n",
+        );
+        let chunks: Vec<Result<bytes::Bytes, APIError>> = vec![Ok(body.into())];
+        let items: Vec<_> = ChatCompletionStream::new(futures_util::stream::iter(chunks))
+            .collect()
+            .await;
+
+        assert_eq!(items.len(), 2);
+        match &items[0] {
+            Ok(ChatCompletionStreamResponse::ToolCall(calls)) => {
+                assert_eq!(calls.len(), 1);
+                assert_eq!(calls[0].id, "call_1");
+                assert_eq!(calls[0].function.name.as_deref(), Some("price"));
+                assert_eq!(
+                    calls[0].function.arguments.as_deref(),
+                    Some(r#"{"coin":"btc"}"#)
+                );
+            }
+            other => panic!("Expected tool calls, got {other:?}"),
+        }
+        assert!(matches!(items[1], Ok(ChatCompletionStreamResponse::Done)));
+
+        // Calls still pending at `[DONE]` come out before `Done`.
+        let body = body.replace(
+            "data: {\"choices\":[{\"index\":0,\"delta\":{},\"finish_reason\":\"tool_calls\"}]}\n\n",
+            "",
+        );
+        let chunks: Vec<Result<bytes::Bytes, APIError>> = vec![Ok(body.into())];
+        let items: Vec<_> = ChatCompletionStream::new(futures_util::stream::iter(chunks))
+            .collect()
+            .await;
+        assert!(
+            matches!(&items[0], Ok(ChatCompletionStreamResponse::ToolCall(calls)) if calls.len() == 1)
+        );
+        assert!(matches!(items[1], Ok(ChatCompletionStreamResponse::Done)));
+
+        // Content in the finishing chunk is yielded before the calls.
+        let body = concat!(
+            "data: {\"choices\":[{\"index\":0,\"delta\":{\"tool_calls\":[",
+            "{\"index\":0,\"id\":\"call_1\",\"function\":{\"name\":\"price\",\"arguments\":\"{}\"}}]}}]}\n\n",
+            "data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Checking.\"},",
+            "\"finish_reason\":\"tool_calls\"}]}\n\n",
+            "data: [DONE]\n\n",
+        );
+        let chunks: Vec<Result<bytes::Bytes, APIError>> = vec![Ok(body.into())];
+        let items: Vec<_> = ChatCompletionStream::new(futures_util::stream::iter(chunks))
+            .collect()
+            .await;
+        assert_eq!(items.len(), 3);
+        assert!(
+            matches!(&items[0], Ok(ChatCompletionStreamResponse::Content(content)) if content == "Checking.")
+        );
+        assert!(
+            matches!(&items[1], Ok(ChatCompletionStreamResponse::ToolCall(calls)) if calls.len() == 1)
+        );
+        assert!(matches!(items[2], Ok(ChatCompletionStreamResponse::Done)));
+    }
+
+    #[tokio::test]
+    async fn test_collect_response_rejects_truncated_stream() {
+        let chunks: Vec<Result<bytes::Bytes, APIError>> = vec![Ok(
+            "data: {\"id\":\"c1\",\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Hel\"}}]}\n\n"
+                .into(),
+        )];
+        let err = ChatCompletionChunkStream::new(futures_util::stream::iter(chunks))
+            .collect_response()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, APIError::StreamError { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_stream_reports_errors_and_done() {
+        use futures_util::StreamExt;
+
+        let chunks: Vec<Result<bytes::Bytes, APIError>> = vec![
+            Ok(
+                "data: {\"choices\":[{\"delta\":{\"content\":\"a\"}}]}\n\ndata: not json\n\n"
+                    .into(),
+            ),
+            Ok("data: {\"error\":{\"message\":\"overloaded\"}}\n\ndata: [DONE]\n\n".into()),
+        ];
+        let mut stream = ChatCompletionStream::new(futures_util::stream::iter(chunks));
+
+        assert!(matches!(
+            stream.next().await,
+            Some(Ok(ChatCompletionStreamResponse::Content(content))) if content == "a"
+        ));
+        assert!(matches!(
+            stream.next().await,
+            Some(Err(APIError::DeserializeError { body, .. })) if body == "not json"
+        ));
+        assert!(matches!(
+            stream.next().await,
+            Some(Err(APIError::StreamError { message })) if message == "overloaded"
+        ));
+        assert!(!stream.is_done());
+        assert!(matches!(
+            stream.next().await,
+            Some(Ok(ChatCompletionStreamResponse::Done))
+        ));
+        assert!(stream.next().await.is_none());
+        assert!(stream.is_done());
+    }
+
+    #[tokio::test]
+    async fn test_chunk_stream_keeps_all_choices_and_usage() {
+        use futures_util::StreamExt;
+
+        let body = concat!(
+            "data: {\"id\":\"chatcmpl-1\",\"object\":\"chat.completion.chunk\",\"created\":1,",
+            "\"model\":\"gpt-4o\",\"system_fingerprint\":\"fp_1\",\"choices\":[",
+            "{\"index\":0,\"delta\":{\"role\":\"assistant\",\"content\":\"A\"},\"finish_reason\":null},",
+            "{\"index\":1,\"delta\":{\"tool_calls\":[{\"index\":0,\"id\":\"call_1\",\"type\":\"function\",",
+            "\"function\":{\"name\":\"lookup\",\"arguments\":\"\"}}]},\"finish_reason\":null}]}\n\n",
+            "data: {\"id\":\"chatcmpl-1\",\"object\":\"chat.completion.chunk\",\"created\":1,",
+            "\"model\":\"gpt-4o\",\"choices\":[{\"index\":0,\"delta\":{},\"finish_reason\":\"length\"},",
+            "{\"index\":1,\"delta\":{},\"finish_reason\":\"tool_calls\"}]}\n\n",
+            "data: {\"id\":\"chatcmpl-1\",\"object\":\"chat.completion.chunk\",\"created\":1,",
+            "\"model\":\"gpt-4o\",\"choices\":[],",
+            "\"usage\":{\"prompt_tokens\":5,\"completion_tokens\":7,\"total_tokens\":12}}\n\n",
+            "data: [DONE]\n\n",
+        );
+        let chunks: Vec<Result<bytes::Bytes, APIError>> = vec![Ok(body.into())];
+        let mut stream = ChatCompletionChunkStream::new(futures_util::stream::iter(chunks));
+
+        let first = stream.next().await.unwrap().unwrap();
+        assert_eq!(first.system_fingerprint.as_deref(), Some("fp_1"));
+        assert_eq!(first.choices.len(), 2);
+        assert_eq!(first.choices[0].delta.role, Some(MessageRole::assistant));
+        assert_eq!(first.choices[0].delta.content.as_deref(), Some("A"));
+        let tool_call = &first.choices[1].delta.tool_calls.as_ref().unwrap()[0];
+        assert_eq!(tool_call.id.as_deref(), Some("call_1"));
+
+        let second = stream.next().await.unwrap().unwrap();
+        assert_eq!(second.choices[0].finish_reason, Some(FinishReason::length));
+        assert_eq!(
+            second.choices[1].finish_reason,
+            Some(FinishReason::tool_calls)
+        );
+
+        let last = stream.next().await.unwrap().unwrap();
+        assert!(last.choices.is_empty());
+        assert_eq!(last.usage.unwrap().total_tokens, 12);
+
+        assert!(stream.next().await.is_none());
+        assert!(stream.is_done());
+    }
 }
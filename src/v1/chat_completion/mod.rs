@@ -1,11 +1,16 @@
 use crate::v1::types;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
 use serde::de::{self, MapAccess, SeqAccess, Visitor};
 use serde::ser::SerializeMap;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::fmt;
+use std::path::Path;
+use std::{fmt, fs, io};
 
 #[allow(clippy::module_inception)]
 pub mod chat_completion;
+pub mod chat_completion_accumulator;
+pub mod chat_completion_store;
 pub mod chat_completion_stream;
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -13,7 +18,7 @@ pub enum ToolChoiceType {
     None,
     Auto,
     Required,
-    ToolChoice { tool: Tool },
+    ToolChoice { tool: Box<Tool> },
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -43,11 +48,238 @@ pub struct Reasoning {
     pub summary: Option<ReasoningSummary>,
 }
 
+/// Output format of a chat completion.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseFormat {
+    Text,
+    JsonObject,
+    JsonSchema { json_schema: JsonSchemaFormat },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct JsonSchemaFormat {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Modality {
+    Text,
+    Audio,
+}
+
+/// Voice and encoding of spoken output, required when `modalities`
+/// includes `audio`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct AudioOutputConfig {
+    /// e.g. `alloy`, `ash`, `coral`, `sage` or `verse`.
+    pub voice: String,
+    pub format: AudioOutputFormat,
+}
+
+impl AudioOutputConfig {
+    pub fn new(voice: impl Into<String>, format: AudioOutputFormat) -> Self {
+        Self {
+            voice: voice.into(),
+            format,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioOutputFormat {
+    Wav,
+    Aac,
+    Mp3,
+    Flac,
+    Opus,
+    Pcm16,
+}
+
+/// Known output for Predicted Outputs, e.g. a file being edited; matching
+/// tokens are returned faster.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Prediction {
+    Content { content: String },
+}
+
+impl Prediction {
+    pub fn content(content: impl Into<String>) -> Self {
+        Prediction::Content {
+            content: content.into(),
+        }
+    }
+}
+
+/// Spoken answer of an audio-capable model.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct ChatCompletionAudio {
+    /// Sent back in a later assistant message to refer to this answer.
+    pub id: String,
+    /// The audio in the requested format, decoded from base64.
+    #[serde(with = "base64_data")]
+    pub data: Vec<u8>,
+    /// Unix time after which `id` can no longer be referenced.
+    pub expires_at: i64,
+    pub transcript: String,
+}
+
+impl ChatCompletionAudio {
+    /// A reference to this answer for the follow-up assistant message.
+    pub fn reference(&self) -> AudioReference {
+        AudioReference {
+            id: self.id.clone(),
+        }
+    }
+}
+
+/// A previous audio answer, set on an assistant message in place of its
+/// content.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct AudioReference {
+    pub id: String,
+}
+
+/// Audio payloads are base64 strings on the wire and bytes in Rust.
+pub(crate) mod base64_data {
+    use super::BASE64;
+    use base64::Engine;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&BASE64.encode(data))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let data = String::deserialize(deserializer)?;
+        BASE64.decode(data).map_err(serde::de::Error::custom)
+    }
+
+    pub mod option {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(
+            data: &Option<Vec<u8>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match data {
+                Some(data) => super::serialize(data, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Vec<u8>>, D::Error> {
+            Option::<String>::deserialize(deserializer)?
+                .map(|data| BASE64.decode(data).map_err(serde::de::Error::custom))
+                .transpose()
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ServiceTier {
+    Auto,
+    Default,
+    Flex,
+    Scale,
+    Priority,
+    /// A tier this version does not know about yet.
+    #[serde(untagged)]
+    Other(String),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Verbosity {
+    Low,
+    Medium,
+    High,
+}
+
+/// Options for the built-in web search of the `*-search-preview` models.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct WebSearchOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_context_size: Option<SearchContextSize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_location: Option<UserLocation>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchContextSize {
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum UserLocation {
+    Approximate { approximate: ApproximateLocation },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct ApproximateLocation {
+    /// Two-letter ISO country code.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    /// IANA time zone, e.g. `America/Los_Angeles`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
+
+/// Log probabilities of the tokens in a choice.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ChoiceLogprobs {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<Vec<TokenLogprob>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refusal: Option<Vec<TokenLogprob>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TokenLogprob {
+    pub token: String,
+    pub logprob: f64,
+    /// UTF-8 bytes of the token, for tokens that are not valid UTF-8 alone.
+    #[serde(default)]
+    pub bytes: Option<Vec<u8>>,
+    /// The most likely tokens at this position, up to `top_logprobs`.
+    #[serde(default)]
+    pub top_logprobs: Vec<TopLogprob>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TopLogprob {
+    pub token: String,
+    pub logprob: f64,
+    #[serde(default)]
+    pub bytes: Option<Vec<u8>>,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
 #[allow(non_camel_case_types)]
 pub enum MessageRole {
     user,
     system,
+    developer,
     assistant,
     function,
     tool,
@@ -56,7 +288,10 @@ pub enum MessageRole {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Content {
     Text(String),
+    /// Legacy form of `Parts` that only holds text and images. Arrays are
+    /// deserialized as `Parts`.
     ImageUrl(Vec<ImageUrl>),
+    Parts(Vec<ContentPart>),
 }
 
 impl serde::Serialize for Content {
@@ -73,6 +308,7 @@ impl serde::Serialize for Content {
                 }
             }
             Content::ImageUrl(ref image_url) => image_url.serialize(serializer),
+            Content::Parts(ref parts) => parts.serialize(serializer),
         }
     }
 }
@@ -102,18 +338,18 @@ impl<'de> Deserialize<'de> for Content {
             where
                 A: SeqAccess<'de>,
             {
-                let image_urls: Vec<ImageUrl> =
+                let parts: Vec<ContentPart> =
                     Deserialize::deserialize(de::value::SeqAccessDeserializer::new(seq))?;
-                Ok(Content::ImageUrl(image_urls))
+                Ok(Content::Parts(parts))
             }
 
             fn visit_map<M>(self, map: M) -> Result<Content, M::Error>
             where
                 M: MapAccess<'de>,
             {
-                let image_urls: Vec<ImageUrl> =
+                let part: ContentPart =
                     Deserialize::deserialize(de::value::MapAccessDeserializer::new(map))?;
-                Ok(Content::ImageUrl(image_urls))
+                Ok(Content::Parts(vec![part]))
             }
 
             fn visit_none<E>(self) -> Result<Self::Value, E>
@@ -135,17 +371,237 @@ impl<'de> Deserialize<'de> for Content {
     }
 }
 
+impl From<Vec<ContentPart>> for Content {
+    fn from(parts: Vec<ContentPart>) -> Self {
+        Content::Parts(parts)
+    }
+}
+
+/// One part of a multimodal message.
+///
+/// ```no_run
+/// # use openai_api_rs::v1::chat_completion::{Content, ContentPart, ImageDetail};
+/// # fn run() -> std::io::Result<()> {
+/// let content = Content::Parts(vec![
+///     ContentPart::text("Compare the chart with the report."),
+///     ContentPart::image_file("chart.png")?.with_detail(ImageDetail::High),
+///     ContentPart::file_path("report.pdf")?,
+/// ]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentPart {
+    Text {
+        text: String,
+    },
+    ImageUrl {
+        image_url: ImageUrlType,
+    },
+    InputAudio {
+        input_audio: InputAudio,
+    },
+    File {
+        file: FileContent,
+    },
+    /// Only in assistant messages.
+    Refusal {
+        refusal: String,
+    },
+    #[serde(untagged)]
+    Other(serde_json::Value),
+}
+
+impl ContentPart {
+    pub fn text(text: impl Into<String>) -> Self {
+        ContentPart::Text { text: text.into() }
+    }
+
+    /// An image by URL, which may be a data URL.
+    pub fn image_url(url: impl Into<String>) -> Self {
+        ContentPart::ImageUrl {
+            image_url: ImageUrlType {
+                url: url.into(),
+                detail: None,
+            },
+        }
+    }
+
+    /// An image sent inline. Fails if `bytes` is not a PNG, JPEG, GIF or
+    /// WebP image.
+    pub fn image_bytes(bytes: &[u8]) -> io::Result<Self> {
+        match sniff_mime(bytes) {
+            Some(mime) if mime.starts_with("image/") => Ok(Self::image_url(data_url(mime, bytes))),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not a PNG, JPEG, GIF or WebP image",
+            )),
+        }
+    }
+
+    /// Reads an image from disk and sends it inline.
+    pub fn image_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::image_bytes(&fs::read(path)?)
+    }
+
+    /// Sets the detail level of an image part; other parts are unchanged.
+    pub fn with_detail(mut self, detail: ImageDetail) -> Self {
+        if let ContentPart::ImageUrl { image_url } = &mut self {
+            image_url.detail = Some(detail);
+        }
+        self
+    }
+
+    /// Audio sent inline.
+    pub fn input_audio(bytes: &[u8], format: InputAudioFormat) -> Self {
+        ContentPart::InputAudio {
+            input_audio: InputAudio {
+                data: BASE64.encode(bytes),
+                format,
+            },
+        }
+    }
+
+    /// Reads a WAV or MP3 file from disk and sends it inline.
+    pub fn audio_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+        let format = match sniff_mime(&bytes) {
+            Some("audio/wav") => InputAudioFormat::Wav,
+            Some("audio/mpeg") => InputAudioFormat::Mp3,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "not a WAV or MP3 file",
+                ))
+            }
+        };
+        Ok(Self::input_audio(&bytes, format))
+    }
+
+    /// A file uploaded with purpose `user_data`.
+    pub fn file_id(file_id: impl Into<String>) -> Self {
+        ContentPart::File {
+            file: FileContent {
+                file_id: Some(file_id.into()),
+                file_data: None,
+                filename: None,
+            },
+        }
+    }
+
+    /// A file sent inline, e.g. a PDF.
+    pub fn file_bytes(filename: impl Into<String>, bytes: &[u8]) -> Self {
+        let filename = filename.into();
+        let mime = sniff_mime(bytes)
+            .or_else(|| mime_from_extension(Path::new(&filename)))
+            .unwrap_or("application/octet-stream");
+        ContentPart::File {
+            file: FileContent {
+                file_id: None,
+                file_data: Some(data_url(mime, bytes)),
+                filename: Some(filename),
+            },
+        }
+    }
+
+    /// Reads a file from disk and sends it inline under its file name.
+    pub fn file_path(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let filename = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Ok(Self::file_bytes(filename, &fs::read(path)?))
+    }
+}
+
+/// A base64 `data:` URL for `bytes`.
+pub fn data_url(mime: &str, bytes: &[u8]) -> String {
+    format!("data:{mime};base64,{}", BASE64.encode(bytes))
+}
+
+/// The MIME type of the formats accepted in content parts, from their magic
+/// bytes.
+fn sniff_mime(bytes: &[u8]) -> Option<&'static str> {
+    let mime = match bytes {
+        [0x89, b'P', b'N', b'G', ..] => "image/png",
+        [0xFF, 0xD8, 0xFF, ..] => "image/jpeg",
+        [b'G', b'I', b'F', b'8', ..] => "image/gif",
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => "image/webp",
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'A', b'V', b'E', ..] => "audio/wav",
+        [b'I', b'D', b'3', ..] => "audio/mpeg",
+        [0xFF, second, ..] if second & 0xE0 == 0xE0 => "audio/mpeg",
+        [b'%', b'P', b'D', b'F', ..] => "application/pdf",
+        _ => return None,
+    };
+    Some(mime)
+}
+
+fn mime_from_extension(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    let mime = match extension.as_str() {
+        "pdf" => "application/pdf",
+        "txt" => "text/plain",
+        "md" => "text/markdown",
+        "csv" => "text/csv",
+        "json" => "application/json",
+        "html" => "text/html",
+        _ => return None,
+    };
+    Some(mime)
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
 #[allow(non_camel_case_types)]
 pub enum ContentType {
     text,
     image_url,
+    input_audio,
+    file,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
 #[allow(non_camel_case_types)]
 pub struct ImageUrlType {
     pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<ImageDetail>,
+}
+
+/// How closely the model looks at an image; `low` costs a fixed, small
+/// number of tokens.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageDetail {
+    Auto,
+    Low,
+    High,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct InputAudio {
+    /// Base64-encoded audio.
+    pub data: String,
+    pub format: InputAudioFormat,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum InputAudioFormat {
+    Wav,
+    Mp3,
+}
+
+/// A file by id, or inline as a data URL.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct FileContent {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_data: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filename: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
@@ -168,6 +624,9 @@ pub struct ChatCompletionMessage {
     pub tool_calls: Option<Vec<ToolCall>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_call_id: Option<String>,
+    /// A previous audio answer of the assistant, for multi-turn audio.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio: Option<AudioReference>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -175,6 +634,9 @@ pub struct ChatCompletionMessageForResponse {
     pub role: MessageRole,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content: Option<String>,
+    /// Set instead of `content` when the model declines a structured output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refusal: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(alias = "reasoning")]
     pub reasoning_content: Option<String>,
@@ -182,12 +644,18 @@ pub struct ChatCompletionMessageForResponse {
     pub name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_calls: Option<Vec<ToolCall>>,
+    /// Set when `modalities` includes `audio`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio: Option<ChatCompletionAudio>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ChatCompletionChoice {
     pub index: i64,
     pub message: ChatCompletionMessageForResponse,
+    /// Set when `logprobs` was requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logprobs: Option<ChoiceLogprobs>,
     pub finish_reason: Option<FinishReason>,
     pub finish_details: Option<FinishDetails>,
 }
@@ -199,6 +667,7 @@ pub enum FinishReason {
     length,
     content_filter,
     tool_calls,
+    function_call,
     null,
 }
 
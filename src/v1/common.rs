@@ -1,10 +1,36 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct Usage {
     pub prompt_tokens: i32,
     pub completion_tokens: i32,
     pub total_tokens: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_tokens_details: Option<PromptTokensDetails>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completion_tokens_details: Option<CompletionTokensDetails>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct PromptTokensDetails {
+    /// Prompt tokens served from the prompt cache.
+    #[serde(default)]
+    pub cached_tokens: i32,
+    #[serde(default)]
+    pub audio_tokens: i32,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct CompletionTokensDetails {
+    /// Tokens spent on hidden reasoning; billed but not in the output.
+    #[serde(default)]
+    pub reasoning_tokens: i32,
+    #[serde(default)]
+    pub audio_tokens: i32,
+    #[serde(default)]
+    pub accepted_prediction_tokens: i32,
+    #[serde(default)]
+    pub rejected_prediction_tokens: i32,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::impl_builder_methods;
+use crate::v1::pagination::{CursorPage, PageItem};
+use crate::v1::responses::responses::InputItem;
+
+/// An item stored in a conversation. Items come back in the shape they were
+/// added in, so messages, function calls and their outputs share the input
+/// item type; other item kinds are kept as `InputItem::Other`.
+pub type ConversationItem = InputItem;
+
+pub type ConversationItemList = CursorPage<ConversationItem>;
+
+impl PageItem for InputItem {
+    fn page_id(&self) -> &str {
+        let id = match self {
+            InputItem::Message(message) => message.id.as_deref(),
+            InputItem::FunctionCall(call) => call.id.as_deref(),
+            InputItem::FunctionCallOutput { id, .. } => id.as_deref(),
+            InputItem::Reasoning(reasoning) => Some(reasoning.id.as_str()),
+            InputItem::ItemReference { id } => Some(id.as_str()),
+            InputItem::Other(value) => value.get("id").and_then(|id| id.as_str()),
+        };
+        id.unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct CreateConversationRequest {
+    /// Initial items, up to 20.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<Vec<InputItem>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+impl CreateConversationRequest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl_builder_methods!(
+    CreateConversationRequest,
+    items: Vec<InputItem>,
+    metadata: HashMap<String, String>
+);
+
+#[derive(Debug, Serialize, Clone)]
+pub struct UpdateConversationRequest {
+    pub metadata: HashMap<String, String>,
+}
+
+impl UpdateConversationRequest {
+    pub fn new(metadata: HashMap<String, String>) -> Self {
+        Self { metadata }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CreateConversationItemsRequest {
+    /// Items to append, up to 20.
+    pub items: Vec<InputItem>,
+}
+
+impl CreateConversationItemsRequest {
+    pub fn new(items: Vec<InputItem>) -> Self {
+        Self { items }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ConversationObject {
+    pub id: String,
+    pub object: String,
+    pub created_at: i64,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_item_list_deserialization() {
+        let list: ConversationItemList = serde_json::from_value(json!({
+            "object": "list",
+            "data": [
+                {"type": "message", "id": "msg_1", "status": "completed", "role": "user",
+                 "content": [{"type": "input_text", "text": "Hello"}]},
+                {"type": "message", "id": "msg_2", "status": "completed", "role": "assistant",
+                 "content": [{"type": "output_text", "text": "Hi!", "annotations": []}]},
+                {"type": "web_search_call", "id": "ws_1", "status": "completed"}
+            ],
+            "first_id": "msg_1",
+            "last_id": "ws_1",
+            "has_more": false
+        }))
+        .unwrap();
+
+        assert!(matches!(&list.data[0], InputItem::Message(m) if m.id.as_deref() == Some("msg_1")));
+        assert_eq!(list.data[1].page_id(), "msg_2");
+        assert_eq!(list.data[2].page_id(), "ws_1");
+    }
+}
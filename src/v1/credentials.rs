@@ -0,0 +1,242 @@
+use crate::v1::error::APIError;
+use futures_util::future::BoxFuture;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Supplies the API key or bearer token sent with each request.
+///
+/// `OpenAIClient` and `RealtimeClient` ask the provider for a credential
+/// before every request, so rotated keys are picked up without rebuilding
+/// the client. When the API answers `401 Unauthorized`, the client calls
+/// `invalidate` and, if it returns `true`, repeats the request once with a
+/// freshly fetched credential.
+pub trait CredentialProvider: Send + Sync {
+    fn credential(&self) -> BoxFuture<'_, Result<String, APIError>>;
+
+    /// Drop any cached credential. Returns whether fetching again may
+    /// produce a different one.
+    fn invalidate(&self) -> bool {
+        false
+    }
+}
+
+impl<P: CredentialProvider + ?Sized> CredentialProvider for Arc<P> {
+    fn credential(&self) -> BoxFuture<'_, Result<String, APIError>> {
+        (**self).credential()
+    }
+
+    fn invalidate(&self) -> bool {
+        (**self).invalidate()
+    }
+}
+
+impl fmt::Debug for dyn CredentialProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CredentialProvider")
+    }
+}
+
+/// A fixed key, as set by `OpenAIClientBuilder::with_api_key`.
+#[derive(Clone)]
+pub struct StaticCredential {
+    credential: String,
+}
+
+impl StaticCredential {
+    pub fn new(credential: impl Into<String>) -> Self {
+        Self {
+            credential: credential.into(),
+        }
+    }
+}
+
+impl CredentialProvider for StaticCredential {
+    fn credential(&self) -> BoxFuture<'_, Result<String, APIError>> {
+        Box::pin(async move { Ok(self.credential.clone()) })
+    }
+}
+
+/// Reads the key from an environment variable on every request.
+#[derive(Debug, Clone)]
+pub struct EnvCredential {
+    var: String,
+}
+
+impl EnvCredential {
+    pub fn new(var: impl Into<String>) -> Self {
+        Self { var: var.into() }
+    }
+}
+
+impl CredentialProvider for EnvCredential {
+    fn credential(&self) -> BoxFuture<'_, Result<String, APIError>> {
+        Box::pin(async move {
+            std::env::var(&self.var).map_err(|err| APIError::InvalidRequest {
+                message: format!("Failed to read credential from ${}: {err}", self.var),
+            })
+        })
+    }
+}
+
+/// Reads the key from a file, re-reading it whenever its modification time
+/// changes. Surrounding whitespace is trimmed.
+#[derive(Debug)]
+pub struct FileCredential {
+    path: PathBuf,
+    cached: Mutex<Option<(SystemTime, String)>>,
+}
+
+impl FileCredential {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            cached: Mutex::new(None),
+        }
+    }
+
+    async fn read(&self) -> Result<String, APIError> {
+        let modified = tokio::fs::metadata(&self.path).await?.modified()?;
+        if let Some((cached_at, credential)) = &*self.cached.lock().unwrap() {
+            if *cached_at == modified {
+                return Ok(credential.clone());
+            }
+        }
+
+        let credential = tokio::fs::read_to_string(&self.path)
+            .await?
+            .trim()
+            .to_string();
+        *self.cached.lock().unwrap() = Some((modified, credential.clone()));
+        Ok(credential)
+    }
+}
+
+impl CredentialProvider for FileCredential {
+    fn credential(&self) -> BoxFuture<'_, Result<String, APIError>> {
+        Box::pin(self.read())
+    }
+
+    /// Re-reads the file and reports a change only if it now holds a
+    /// different key, since retrying with the same one would fail again.
+    fn invalidate(&self) -> bool {
+        let current = std::fs::read_to_string(&self.path)
+            .ok()
+            .map(|contents| contents.trim().to_string());
+        let mut cached = self.cached.lock().unwrap();
+        let changed = match (&*cached, current) {
+            (Some((_, cached)), Some(current)) => *cached != current,
+            _ => false,
+        };
+        if changed {
+            *cached = None;
+        }
+        changed
+    }
+}
+
+/// Caches the credential from another provider for `ttl`, e.g. to avoid a
+/// secrets-manager round trip on every request.
+pub struct CachedCredential<P> {
+    inner: P,
+    ttl: Duration,
+    cached: Mutex<Option<(Instant, String)>>,
+}
+
+impl<P: CredentialProvider> CachedCredential<P> {
+    pub fn new(inner: P, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            cached: Mutex::new(None),
+        }
+    }
+
+    async fn get(&self) -> Result<String, APIError> {
+        if let Some((fetched_at, credential)) = &*self.cached.lock().unwrap() {
+            if fetched_at.elapsed() < self.ttl {
+                return Ok(credential.clone());
+            }
+        }
+
+        let credential = self.inner.credential().await?;
+        *self.cached.lock().unwrap() = Some((Instant::now(), credential.clone()));
+        Ok(credential)
+    }
+}
+
+impl<P: CredentialProvider> CredentialProvider for CachedCredential<P> {
+    fn credential(&self) -> BoxFuture<'_, Result<String, APIError>> {
+        Box::pin(self.get())
+    }
+
+    fn invalidate(&self) -> bool {
+        self.inner.invalidate();
+        self.cached.lock().unwrap().take();
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Counting(AtomicUsize);
+
+    impl CredentialProvider for Counting {
+        fn credential(&self) -> BoxFuture<'_, Result<String, APIError>> {
+            let n = self.0.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async move { Ok(format!("key-{n}")) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_credential() {
+        let provider =
+            CachedCredential::new(Counting(AtomicUsize::new(0)), Duration::from_secs(60));
+        assert_eq!(provider.credential().await.unwrap(), "key-0");
+        assert_eq!(provider.credential().await.unwrap(), "key-0");
+        assert!(provider.invalidate());
+        assert_eq!(provider.credential().await.unwrap(), "key-1");
+    }
+
+    #[tokio::test]
+    async fn test_file_credential_rereads_on_change() {
+        let path = std::env::temp_dir().join("openai-api-rs-file-credential");
+        std::fs::write(&path, "sk-old\n").unwrap();
+        let provider = FileCredential::new(&path);
+        assert_eq!(provider.credential().await.unwrap(), "sk-old");
+
+        std::fs::write(&path, "sk-new\n").unwrap();
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(5))
+            .unwrap();
+        assert_eq!(provider.credential().await.unwrap(), "sk-new");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_file_credential_invalidate_only_on_new_key() {
+        let path = std::env::temp_dir().join("openai-api-rs-file-credential-invalidate");
+        std::fs::write(&path, "sk-old\n").unwrap();
+        let provider = FileCredential::new(&path);
+        assert_eq!(provider.credential().await.unwrap(), "sk-old");
+        assert!(!provider.invalidate());
+
+        std::fs::write(&path, "sk-new\n").unwrap();
+        assert!(provider.invalidate());
+        assert_eq!(provider.credential().await.unwrap(), "sk-new");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_env_credential_missing_var() {
+        let provider = EnvCredential::new("OPENAI_API_RS_TEST_MISSING_KEY");
+        assert!(matches!(
+            provider.credential().await,
+            Err(APIError::InvalidRequest { .. })
+        ));
+    }
+}
@@ -1,26 +1,279 @@
-use reqwest::{self};
+use reqwest::header::HeaderMap;
+use reqwest::{self, StatusCode};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
 use std::error::Error;
 use std::fmt;
 
+/// The `error` object returned in the body of a failed OpenAI API call.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ErrorObject {
+    pub message: String,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub error_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub param: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_error_code",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub code: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorBody {
+    error: ErrorObject,
+}
+
+impl ErrorObject {
+    /// Parses an error body of the form `{"error": {...}}`.
+    pub fn from_body(body: &str) -> Option<Self> {
+        serde_json::from_str::<ErrorBody>(body)
+            .ok()
+            .map(|body| body.error)
+    }
+}
+
+// Some OpenAI-compatible providers send numeric error codes.
+fn deserialize_error_code<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match Option::<Value>::deserialize(deserializer)? {
+        Some(Value::String(code)) => Some(code),
+        Some(Value::Null) | None => None,
+        Some(other) => Some(other.to_string()),
+    })
+}
+
+/// A non-success HTTP response from the API.
+#[derive(Debug)]
+pub struct ApiErrorResponse {
+    pub status: StatusCode,
+    /// The parsed `error` object, if the body contained one.
+    pub error: Option<ErrorObject>,
+    pub headers: HeaderMap,
+    /// The raw response body.
+    pub body: String,
+}
+
+/// Why a structured output could not be turned into the requested type.
+#[derive(Debug)]
+pub enum ParseError {
+    /// The model declined to answer; holds its explanation.
+    Refusal(String),
+    /// Generation stopped early, e.g. at `max_tokens` or a content filter,
+    /// so the JSON is incomplete.
+    Incomplete { reason: String },
+    /// The response carried no text to parse.
+    MissingContent,
+    /// The text is not valid JSON for the requested type.
+    Json {
+        source: serde_json::Error,
+        content: String,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Refusal(refusal) => write!(f, "model refused: {refusal}"),
+            ParseError::Incomplete { reason } => write!(f, "output is incomplete: {reason}"),
+            ParseError::MissingContent => write!(f, "response has no content"),
+            ParseError::Json { source, content } => {
+                write!(f, "invalid JSON output: {source} / content {content}")
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum APIError {
     ReqwestError(reqwest::Error),
-    CustomError { message: String },
+    /// The server answered with a non-success status code.
+    ApiError(Box<ApiErrorResponse>),
+    /// The response body could not be deserialized into the expected type.
+    DeserializeError {
+        source: serde_json::Error,
+        body: String,
+    },
+    IoError(std::io::Error),
+    StreamError {
+        message: String,
+    },
+    /// No response, or no stream data, arrived within the configured timeout.
+    Timeout {
+        message: String,
+    },
+    /// The request was rejected locally before being sent.
+    InvalidRequest {
+        message: String,
+    },
+    CustomError {
+        message: String,
+    },
+    /// A structured output could not be parsed into the requested type.
+    ParseError(ParseError),
+}
+
+impl APIError {
+    /// HTTP status code of the failed call, if the server answered.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            APIError::ApiError(response) => Some(response.status),
+            APIError::ReqwestError(err) => err.status(),
+            _ => None,
+        }
+    }
+
+    /// The parsed OpenAI error object, if the server sent one.
+    pub fn error_object(&self) -> Option<&ErrorObject> {
+        match self {
+            APIError::ApiError(response) => response.error.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Response headers of the failed call, if the server answered.
+    pub fn headers(&self) -> Option<&HeaderMap> {
+        match self {
+            APIError::ApiError(response) => Some(&response.headers),
+            _ => None,
+        }
+    }
+
+    /// Value of the `x-request-id` response header.
+    pub fn request_id(&self) -> Option<&str> {
+        self.headers()
+            .and_then(|headers| headers.get("x-request-id"))
+            .and_then(|value| value.to_str().ok())
+    }
+
+    /// Value of `error.type` in the error body, e.g. `invalid_request_error`.
+    pub fn error_type(&self) -> Option<&str> {
+        self.error_object()
+            .and_then(|error| error.error_type.as_deref())
+    }
+
+    /// Value of `error.code` in the error body, e.g. `rate_limit_exceeded`.
+    pub fn code(&self) -> Option<&str> {
+        self.error_object().and_then(|error| error.code.as_deref())
+    }
+
+    /// Value of `error.param` in the error body.
+    pub fn param(&self) -> Option<&str> {
+        self.error_object().and_then(|error| error.param.as_deref())
+    }
 }
 
 impl fmt::Display for APIError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             APIError::ReqwestError(err) => write!(f, "ReqwestError: {err}"),
+            APIError::ApiError(response) => {
+                write!(f, "APIError: {}", response.status)?;
+                match &response.error {
+                    Some(error) => write!(f, ": {}", error.message)?,
+                    None if !response.body.is_empty() => write!(f, ": {}", response.body)?,
+                    None => {}
+                }
+                if let Some(request_id) = self.request_id() {
+                    write!(f, " (request id: {request_id})")?;
+                }
+                Ok(())
+            }
+            APIError::DeserializeError { source, body } => {
+                write!(f, "DeserializeError: {source} / response {body}")
+            }
+            APIError::IoError(err) => write!(f, "IoError: {err}"),
+            APIError::StreamError { message } => write!(f, "StreamError: {message}"),
+            APIError::Timeout { message } => write!(f, "Timeout: {message}"),
+            APIError::InvalidRequest { message } => write!(f, "InvalidRequest: {message}"),
             APIError::CustomError { message } => write!(f, "APIError: {message}"),
+            APIError::ParseError(err) => write!(f, "ParseError: {err}"),
         }
     }
 }
 
-impl Error for APIError {}
+impl Error for APIError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            APIError::ReqwestError(err) => Some(err),
+            APIError::DeserializeError { source, .. } => Some(source),
+            APIError::IoError(err) => Some(err),
+            APIError::ParseError(ParseError::Json { source, .. }) => Some(source),
+            _ => None,
+        }
+    }
+}
 
 impl From<reqwest::Error> for APIError {
     fn from(err: reqwest::Error) -> APIError {
         APIError::ReqwestError(err)
     }
 }
+
+impl From<ParseError> for APIError {
+    fn from(err: ParseError) -> APIError {
+        APIError::ParseError(err)
+    }
+}
+
+impl From<std::io::Error> for APIError {
+    fn from(err: std::io::Error) -> APIError {
+        APIError::IoError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_error_object_from_body() {
+        let body = r#"{
+            "error": {
+                "message": "Rate limit reached",
+                "type": "requests",
+                "param": null,
+                "code": "rate_limit_exceeded"
+            }
+        }"#;
+        let error = ErrorObject::from_body(body).unwrap();
+        assert_eq!(error.message, "Rate limit reached");
+        assert_eq!(error.error_type.as_deref(), Some("requests"));
+        assert_eq!(error.param, None);
+        assert_eq!(error.code.as_deref(), Some("rate_limit_exceeded"));
+    }
+
+    #[test]
+    fn test_error_object_numeric_code() {
+        let body = r#"{"error": {"message": "Bad gateway", "code": 502}}"#;
+        let error = ErrorObject::from_body(body).unwrap();
+        assert_eq!(error.code.as_deref(), Some("502"));
+    }
+
+    #[test]
+    fn test_api_error_accessors() {
+        let body = r#"{"error": {"message": "Invalid key", "type": "invalid_request_error", "code": "invalid_api_key"}}"#;
+        let mut headers = HeaderMap::new();
+        headers.insert("x-request-id", HeaderValue::from_static("req_123"));
+        let err = APIError::ApiError(Box::new(ApiErrorResponse {
+            status: StatusCode::UNAUTHORIZED,
+            error: ErrorObject::from_body(body),
+            headers,
+            body: body.to_string(),
+        }));
+
+        assert_eq!(err.status(), Some(StatusCode::UNAUTHORIZED));
+        assert_eq!(err.request_id(), Some("req_123"));
+        assert_eq!(err.error_type(), Some("invalid_request_error"));
+        assert_eq!(err.code(), Some("invalid_api_key"));
+        assert_eq!(
+            err.to_string(),
+            "APIError: 401 Unauthorized: Invalid key (request id: req_123)"
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::v1::pagination::{CursorPage, PageItem};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -10,10 +11,12 @@ pub struct FileData {
     pub purpose: String,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
-pub struct FileListResponse {
-    pub object: String,
-    pub data: Vec<FileData>,
+pub type FileListResponse = CursorPage<FileData>;
+
+impl PageItem for FileData {
+    fn page_id(&self) -> &str {
+        &self.id
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::impl_builder_methods;
+use crate::v1::pagination::{CursorPage, PageItem};
 
 #[derive(Debug, Serialize, Clone)]
 pub struct CreateFineTuningJobRequest {
@@ -54,7 +55,6 @@ impl ListFineTuningJobsRequest {
 #[derive(Debug, Serialize)]
 pub struct ListFineTuningJobEventsRequest {
     pub fine_tuning_job_id: String,
-    // TODO pass as query params
     #[serde(skip_serializing_if = "Option::is_none")]
     pub after: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -93,11 +93,18 @@ impl CancelFineTuningJobRequest {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-pub struct FineTuningPagination<T> {
-    pub object: String,
-    pub data: Vec<T>,
-    pub has_more: bool,
+pub type FineTuningPagination<T> = CursorPage<T>;
+
+impl PageItem for FineTuningJobObject {
+    fn page_id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl PageItem for FineTuningJobEvent {
+    fn page_id(&self) -> &str {
+        &self.id
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::impl_builder_methods;
+use crate::v1::pagination::{CursorPage, PageItem};
 
 #[derive(Debug, Serialize, Clone)]
 pub struct CreateMessageRequest {
@@ -103,13 +104,12 @@ pub struct ContentText {
     pub annotations: Vec<ContentTextAnnotations>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-pub struct ListMessage {
-    pub object: String,
-    pub data: Vec<MessageObject>,
-    pub first_id: String,
-    pub last_id: String,
-    pub has_more: bool,
+pub type ListMessage = CursorPage<MessageObject>;
+
+impl PageItem for MessageObject {
+    fn page_id(&self) -> &str {
+        &self.id
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -120,13 +120,12 @@ pub struct MessageFileObject {
     pub message_id: String,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-pub struct ListMessageFile {
-    pub object: String,
-    pub data: Vec<MessageFileObject>,
-    pub first_id: String,
-    pub last_id: String,
-    pub has_more: bool,
+pub type ListMessageFile = CursorPage<MessageFileObject>;
+
+impl PageItem for MessageFileObject {
+    fn page_id(&self) -> &str {
+        &self.id
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -0,0 +1,57 @@
+use crate::v1::error::APIError;
+use reqwest::{Method, Request, Response};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+/// Describes the request a middleware hook is being called for.
+#[derive(Debug, Clone)]
+pub struct RequestInfo {
+    pub method: Method,
+    pub url: Url,
+    /// 1-based attempt number when retries are enabled.
+    pub attempt: u32,
+}
+
+/// Hooks run around every HTTP request made by `OpenAIClient`.
+///
+/// Middleware registered with `OpenAIClientBuilder::with_middleware` applies
+/// to JSON, multipart, raw-bytes and streaming endpoints alike, and to each
+/// retry attempt. `before_send` hooks run in registration order; the other
+/// hooks run in reverse order. For streaming endpoints `after_receive` is
+/// called once the response headers arrive.
+pub trait Middleware: Send + Sync {
+    /// Inspect or modify the request before it is sent. Returning an error
+    /// aborts the call without sending anything.
+    fn before_send(&self, _request: &mut Request) -> Result<(), APIError> {
+        Ok(())
+    }
+
+    /// Called with the response, successful or not, and the time it took.
+    fn after_receive(&self, _request: &RequestInfo, _response: &Response, _elapsed: Duration) {}
+
+    /// Called when the request could not be sent or no response arrived.
+    fn on_error(&self, _request: &RequestInfo, _error: &reqwest::Error, _elapsed: Duration) {}
+}
+
+// Lets callers keep a handle on middleware they register, e.g. to read metrics.
+impl<M: Middleware + ?Sized> Middleware for Arc<M> {
+    fn before_send(&self, request: &mut Request) -> Result<(), APIError> {
+        (**self).before_send(request)
+    }
+
+    fn after_receive(&self, request: &RequestInfo, response: &Response, elapsed: Duration) {
+        (**self).after_receive(request, response, elapsed)
+    }
+
+    fn on_error(&self, request: &RequestInfo, error: &reqwest::Error, elapsed: Duration) {
+        (**self).on_error(request, error, elapsed)
+    }
+}
+
+impl fmt::Debug for dyn Middleware {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Middleware")
+    }
+}
@@ -6,6 +6,7 @@ pub mod audio;
 pub mod batch;
 pub mod chat_completion;
 pub mod completion;
+pub mod conversation;
 pub mod edit;
 pub mod embedding;
 pub mod file;
@@ -22,3 +23,17 @@ pub mod run;
 pub mod thread;
 
 pub mod api;
+pub mod azure;
+pub mod credentials;
+pub mod middleware;
+pub mod pagination;
+pub mod rate_limit;
+pub mod rate_limiter;
+pub mod request_options;
+pub mod retry;
+pub mod schema;
+pub mod sse;
+pub mod tool_registry;
+
+#[cfg(test)]
+mod mock_server;
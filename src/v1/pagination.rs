@@ -0,0 +1,160 @@
+use serde::{Deserialize, Serialize};
+
+/// Query parameters accepted by cursor-paginated list endpoints.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListParams {
+    pub limit: Option<i64>,
+    /// `asc` or `desc` by `created_at`.
+    pub order: Option<String>,
+    pub after: Option<String>,
+    pub before: Option<String>,
+}
+
+impl ListParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_limit(mut self, limit: i64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn with_order(mut self, order: impl Into<String>) -> Self {
+        self.order = Some(order.into());
+        self
+    }
+
+    pub fn with_after(mut self, after: impl Into<String>) -> Self {
+        self.after = Some(after.into());
+        self
+    }
+
+    pub fn with_before(mut self, before: impl Into<String>) -> Self {
+        self.before = Some(before.into());
+        self
+    }
+
+    /// Appends the parameters to `path` as a query string, after any query
+    /// `path` already has.
+    pub(crate) fn to_path(&self, path: &str) -> String {
+        let mut params = url::form_urlencoded::Serializer::new(String::new());
+        if let Some(limit) = self.limit {
+            params.append_pair("limit", &limit.to_string());
+        }
+        if let Some(order) = &self.order {
+            params.append_pair("order", order);
+        }
+        if let Some(after) = &self.after {
+            params.append_pair("after", after);
+        }
+        if let Some(before) = &self.before {
+            params.append_pair("before", before);
+        }
+        let query = params.finish();
+        if query.is_empty() {
+            path.to_string()
+        } else if path.contains('?') {
+            format!("{path}&{query}")
+        } else {
+            format!("{path}?{query}")
+        }
+    }
+}
+
+/// Items of a paginated list, identified by the id used as the `after` cursor.
+pub trait PageItem {
+    fn page_id(&self) -> &str;
+}
+
+/// One page of a cursor-paginated list endpoint.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CursorPage<T> {
+    pub object: String,
+    pub data: Vec<T>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_id: Option<String>,
+    #[serde(default)]
+    pub has_more: bool,
+}
+
+impl<T: PageItem> CursorPage<T> {
+    /// Parameters for the page following this one, if there is one. A
+    /// `before` cursor only applies to the first page and is dropped.
+    pub fn next_params(&self, params: &ListParams) -> Option<ListParams> {
+        if !self.has_more {
+            return None;
+        }
+        let cursor = self
+            .last_id
+            .as_deref()
+            .or_else(|| self.data.last().map(PageItem::page_id))?;
+        Some(ListParams {
+            after: Some(cursor.to_string()),
+            before: None,
+            ..params.clone()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl PageItem for String {
+        fn page_id(&self) -> &str {
+            self
+        }
+    }
+
+    #[test]
+    fn test_list_params_to_path() {
+        assert_eq!(ListParams::new().to_path("assistants"), "assistants");
+        let params = ListParams::new()
+            .with_limit(20)
+            .with_order("desc")
+            .with_after("asst_1");
+        assert_eq!(
+            params.to_path("assistants"),
+            "assistants?limit=20&order=desc&after=asst_1"
+        );
+    }
+
+    #[test]
+    fn test_next_params() {
+        let page: CursorPage<String> = serde_json::from_value(serde_json::json!({
+            "object": "list",
+            "data": ["a", "b"],
+            "has_more": true
+        }))
+        .unwrap();
+        let next = page.next_params(&ListParams::new().with_limit(2)).unwrap();
+        assert_eq!(next, ListParams::new().with_limit(2).with_after("b"));
+
+        let last: CursorPage<String> = serde_json::from_value(serde_json::json!({
+            "object": "list",
+            "data": ["c"],
+            "first_id": "c",
+            "last_id": "c",
+            "has_more": false
+        }))
+        .unwrap();
+        assert_eq!(last.next_params(&next), None);
+    }
+
+    #[test]
+    fn test_next_params_drops_before() {
+        let page: CursorPage<String> = serde_json::from_value(serde_json::json!({
+            "object": "list",
+            "data": ["a", "b"],
+            "has_more": true
+        }))
+        .unwrap();
+        let first = ListParams::new().with_limit(2).with_before("z");
+        let next = page.next_params(&first).unwrap();
+        assert_eq!(next, ListParams::new().with_limit(2).with_after("b"));
+        assert_eq!(next.to_path("models"), "models?limit=2&after=b");
+    }
+}
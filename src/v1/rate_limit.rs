@@ -0,0 +1,197 @@
+use reqwest::header::HeaderMap;
+use std::time::Duration;
+
+/// Rate-limit state reported by the `x-ratelimit-*` response headers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    /// `x-ratelimit-limit-requests`
+    pub limit_requests: Option<u64>,
+    /// `x-ratelimit-limit-tokens`
+    pub limit_tokens: Option<u64>,
+    /// `x-ratelimit-remaining-requests`
+    pub remaining_requests: Option<u64>,
+    /// `x-ratelimit-remaining-tokens`
+    pub remaining_tokens: Option<u64>,
+    /// `x-ratelimit-reset-requests`
+    pub reset_requests: Option<Duration>,
+    /// `x-ratelimit-reset-tokens`
+    pub reset_tokens: Option<Duration>,
+}
+
+impl RateLimitInfo {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        Self {
+            limit_requests: parse_header(headers, "x-ratelimit-limit-requests"),
+            limit_tokens: parse_header(headers, "x-ratelimit-limit-tokens"),
+            remaining_requests: parse_header(headers, "x-ratelimit-remaining-requests"),
+            remaining_tokens: parse_header(headers, "x-ratelimit-remaining-tokens"),
+            reset_requests: header_str(headers, "x-ratelimit-reset-requests")
+                .and_then(parse_duration),
+            reset_tokens: header_str(headers, "x-ratelimit-reset-tokens").and_then(parse_duration),
+        }
+    }
+
+    /// Whether any of the rate-limit headers were present.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Metadata the API attaches to every response.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResponseMeta {
+    /// `x-request-id`
+    pub request_id: Option<String>,
+    /// `openai-model`
+    pub model: Option<String>,
+    /// `openai-organization`
+    pub organization: Option<String>,
+    /// `openai-version`
+    pub version: Option<String>,
+    /// `openai-processing-ms`
+    pub processing_time: Option<Duration>,
+    pub rate_limit: RateLimitInfo,
+}
+
+impl ResponseMeta {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        Self {
+            request_id: header_str(headers, "x-request-id").map(str::to_string),
+            model: header_str(headers, "openai-model").map(str::to_string),
+            organization: header_str(headers, "openai-organization").map(str::to_string),
+            version: header_str(headers, "openai-version").map(str::to_string),
+            processing_time: header_str(headers, "openai-processing-ms")
+                .and_then(|ms| ms.trim().parse::<f64>().ok())
+                .and_then(|ms| duration_from_secs(ms / 1000.0)),
+            rate_limit: RateLimitInfo::from_headers(headers),
+        }
+    }
+}
+
+/// Parses durations in the format used by the `x-ratelimit-reset-*`
+/// headers, e.g. `1s`, `6m0s`, `20ms` or `1h2m3.5s`.
+pub fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+
+    let mut total = 0.0;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let number: f64 = rest[..number_len].parse().ok()?;
+        rest = &rest[number_len..];
+
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(rest.len());
+        let seconds_per_unit = match &rest[..unit_len] {
+            "h" => 3600.0,
+            "m" => 60.0,
+            "s" | "" => 1.0,
+            "ms" => 0.001,
+            "us" | "µs" => 0.000_001,
+            "ns" => 0.000_000_001,
+            _ => return None,
+        };
+        rest = &rest[unit_len..];
+        total += number * seconds_per_unit;
+    }
+
+    duration_from_secs(total)
+}
+
+/// Converts seconds read from a header, rejecting negative, infinite, NaN
+/// and out-of-range values instead of panicking on them.
+pub(crate) fn duration_from_secs(secs: f64) -> Option<Duration> {
+    if !secs.is_finite() || secs < 0.0 {
+        return None;
+    }
+    Duration::try_from_secs_f64(secs).ok()
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+fn parse_header(headers: &HeaderMap, name: &str) -> Option<u64> {
+    header_str(headers, name).and_then(|value| value.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("1s"), Some(Duration::from_secs(1)));
+        assert_eq!(parse_duration("6m0s"), Some(Duration::from_secs(360)));
+        assert_eq!(parse_duration("20ms"), Some(Duration::from_millis(20)));
+        assert_eq!(
+            parse_duration("1h2m3.5s"),
+            Some(Duration::from_millis(3_723_500))
+        );
+        assert_eq!(parse_duration("0.5"), Some(Duration::from_millis(500)));
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("soon"), None);
+        assert_eq!(parse_duration("inf"), None);
+        assert_eq!(parse_duration("1e30s"), None);
+        assert_eq!(parse_duration(&format!("1{}s", "0".repeat(400))), None);
+    }
+
+    #[test]
+    fn test_unusable_processing_time() {
+        for value in ["inf", "NaN", "1e30", "-5"] {
+            let mut headers = HeaderMap::new();
+            headers.insert("openai-processing-ms", HeaderValue::from_static(value));
+            assert_eq!(ResponseMeta::from_headers(&headers).processing_time, None);
+        }
+    }
+
+    #[test]
+    fn test_response_meta_from_headers() {
+        let mut headers = HeaderMap::new();
+        for (name, value) in [
+            ("x-request-id", "req_abc"),
+            ("openai-model", "gpt-4o-2024-08-06"),
+            ("openai-organization", "org-1"),
+            ("openai-processing-ms", "412"),
+            ("x-ratelimit-limit-requests", "5000"),
+            ("x-ratelimit-limit-tokens", "800000"),
+            ("x-ratelimit-remaining-requests", "4999"),
+            ("x-ratelimit-remaining-tokens", "799950"),
+            ("x-ratelimit-reset-requests", "12ms"),
+            ("x-ratelimit-reset-tokens", "6m0s"),
+        ] {
+            headers.insert(name, HeaderValue::from_static(value));
+        }
+
+        let meta = ResponseMeta::from_headers(&headers);
+        assert_eq!(meta.request_id.as_deref(), Some("req_abc"));
+        assert_eq!(meta.model.as_deref(), Some("gpt-4o-2024-08-06"));
+        assert_eq!(meta.organization.as_deref(), Some("org-1"));
+        assert_eq!(meta.version, None);
+        assert_eq!(meta.processing_time, Some(Duration::from_millis(412)));
+        assert_eq!(
+            meta.rate_limit,
+            RateLimitInfo {
+                limit_requests: Some(5000),
+                limit_tokens: Some(800000),
+                remaining_requests: Some(4999),
+                remaining_tokens: Some(799950),
+                reset_requests: Some(Duration::from_millis(12)),
+                reset_tokens: Some(Duration::from_secs(360)),
+            }
+        );
+    }
+
+    #[test]
+    fn test_missing_headers() {
+        let info = RateLimitInfo::from_headers(&HeaderMap::new());
+        assert!(info.is_empty());
+    }
+}
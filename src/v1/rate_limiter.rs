@@ -0,0 +1,394 @@
+use crate::v1::rate_limit::RateLimitInfo;
+use reqwest::header::HeaderMap;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Per-minute budgets for a model.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimits {
+    pub requests_per_minute: Option<u32>,
+    pub tokens_per_minute: Option<u32>,
+}
+
+impl RateLimits {
+    pub fn new(requests_per_minute: u32, tokens_per_minute: u32) -> Self {
+        Self {
+            requests_per_minute: Some(requests_per_minute),
+            tokens_per_minute: Some(tokens_per_minute),
+        }
+    }
+}
+
+/// Client-side request and token budgets shared by every task using a client.
+///
+/// Callers wait in [`RateLimiter::acquire`] until the model has capacity
+/// instead of being rejected with a 429. Budgets are refilled continuously
+/// and corrected from the `x-ratelimit-*` headers of each response; models
+/// without configured limits learn them from those headers.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    default_limits: Option<RateLimits>,
+    model_limits: HashMap<String, RateLimits>,
+    buckets: Mutex<HashMap<String, ModelBuckets>>,
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits applied to every model without its own entry.
+    pub fn with_default_limits(mut self, limits: RateLimits) -> Self {
+        self.default_limits = Some(limits);
+        self
+    }
+
+    pub fn with_model_limits(mut self, model: impl Into<String>, limits: RateLimits) -> Self {
+        self.model_limits.insert(model.into(), limits);
+        self
+    }
+
+    /// Waits until `model` has room for one more request using `tokens` tokens.
+    pub async fn acquire(&self, model: &str, tokens: u32) {
+        loop {
+            let wait = {
+                let mut buckets = self.buckets.lock().unwrap();
+                buckets
+                    .entry(model.to_string())
+                    .or_insert_with(|| self.new_buckets(model))
+                    .try_acquire(tokens, Instant::now())
+            };
+            match wait {
+                None => return,
+                Some(wait) => tokio::time::sleep(wait).await,
+            }
+        }
+    }
+
+    /// Corrects the budgets of `model` from the headers of a response.
+    pub fn update_from_headers(&self, model: &str, headers: &HeaderMap) {
+        let info = RateLimitInfo::from_headers(headers);
+        if info.is_empty() {
+            return;
+        }
+        let mut buckets = self.buckets.lock().unwrap();
+        buckets
+            .entry(model.to_string())
+            .or_insert_with(|| self.new_buckets(model))
+            .update(&info, Instant::now());
+    }
+
+    fn new_buckets(&self, model: &str) -> ModelBuckets {
+        let limits = self
+            .model_limits
+            .get(model)
+            .copied()
+            .or(self.default_limits)
+            .unwrap_or_default();
+        let now = Instant::now();
+        ModelBuckets {
+            requests: limits
+                .requests_per_minute
+                .map(|limit| TokenBucket::per_minute(limit as f64, now)),
+            tokens: limits
+                .tokens_per_minute
+                .map(|limit| TokenBucket::per_minute(limit as f64, now)),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ModelBuckets {
+    requests: Option<TokenBucket>,
+    tokens: Option<TokenBucket>,
+}
+
+impl ModelBuckets {
+    fn try_acquire(&mut self, tokens: u32, now: Instant) -> Option<Duration> {
+        let request_wait = self.requests.as_mut().and_then(|b| b.wait_time(1.0, now));
+        let token_wait = self
+            .tokens
+            .as_mut()
+            .and_then(|b| b.wait_time(tokens as f64, now));
+        let wait = request_wait.max(token_wait);
+        if wait.is_none() {
+            if let Some(bucket) = self.requests.as_mut() {
+                bucket.take(1.0);
+            }
+            if let Some(bucket) = self.tokens.as_mut() {
+                bucket.take(tokens as f64);
+            }
+        }
+        wait
+    }
+
+    fn update(&mut self, info: &RateLimitInfo, now: Instant) {
+        TokenBucket::correct(
+            &mut self.requests,
+            info.limit_requests,
+            info.remaining_requests,
+            info.reset_requests,
+            now,
+        );
+        TokenBucket::correct(
+            &mut self.tokens,
+            info.limit_tokens,
+            info.remaining_tokens,
+            info.reset_tokens,
+            now,
+        );
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    available: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+    paused_until: Option<Instant>,
+}
+
+impl TokenBucket {
+    fn per_minute(limit: f64, now: Instant) -> Self {
+        Self {
+            capacity: limit,
+            available: limit,
+            refill_per_sec: limit / 60.0,
+            last_refill: now,
+            paused_until: None,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.available = (self.available + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    /// How long to wait until `amount` is available, or `None` if it is now.
+    fn wait_time(&mut self, amount: f64, now: Instant) -> Option<Duration> {
+        if let Some(paused_until) = self.paused_until {
+            if paused_until > now {
+                return Some(paused_until - now);
+            }
+            self.paused_until = None;
+        }
+
+        self.refill(now);
+        // Requests larger than the whole budget would otherwise wait forever.
+        let amount = amount.min(self.capacity);
+        if self.available >= amount {
+            return None;
+        }
+        if self.refill_per_sec <= 0.0 {
+            return Some(Duration::from_secs(60));
+        }
+        Some(Duration::from_secs_f64(
+            (amount - self.available) / self.refill_per_sec,
+        ))
+    }
+
+    fn take(&mut self, amount: f64) {
+        self.available = (self.available - amount.min(self.capacity)).max(0.0);
+    }
+
+    fn correct(
+        bucket: &mut Option<TokenBucket>,
+        limit: Option<u64>,
+        remaining: Option<u64>,
+        reset: Option<Duration>,
+        now: Instant,
+    ) {
+        if let Some(limit) = limit {
+            let bucket = bucket.get_or_insert_with(|| Self::per_minute(limit as f64, now));
+            bucket.refill(now);
+            bucket.capacity = limit as f64;
+            bucket.refill_per_sec = limit as f64 / 60.0;
+            bucket.available = bucket.available.min(bucket.capacity);
+        }
+
+        let Some(bucket) = bucket.as_mut() else {
+            return;
+        };
+        if let Some(remaining) = remaining {
+            bucket.refill(now);
+            bucket.available = bucket.available.min(remaining as f64);
+            if remaining == 0 {
+                bucket.paused_until = reset.map(|reset| now + reset);
+            }
+        }
+    }
+}
+
+/// Rough token count of a JSON request body, used to charge token budgets.
+///
+/// Understands chat completion (`messages`, `max_tokens`,
+/// `max_completion_tokens`, `n`), embedding (`input`) and Responses
+/// (`input`, `instructions`, `max_output_tokens`) request bodies. Text is
+/// counted at four characters per token, plus a small overhead per message.
+pub fn estimate_tokens(body: &Value) -> u32 {
+    let mut prompt_chars = 0usize;
+    let mut overhead = 0usize;
+    for field in ["messages", "input", "instructions", "prompt"] {
+        if let Some(value) = body.get(field) {
+            prompt_chars += text_len(value);
+            if let Some(items) = value.as_array() {
+                overhead += 4 * items.len();
+            }
+        }
+    }
+
+    let max_output = ["max_completion_tokens", "max_tokens", "max_output_tokens"]
+        .into_iter()
+        .find_map(|field| body.get(field).and_then(Value::as_u64))
+        .unwrap_or(0);
+    let choices = body.get("n").and_then(Value::as_u64).unwrap_or(1).max(1);
+
+    let prompt_tokens = prompt_chars.div_ceil(4) + overhead;
+    (prompt_tokens as u64 + max_output * choices).min(u32::MAX as u64) as u32
+}
+
+fn text_len(value: &Value) -> usize {
+    match value {
+        Value::String(text) => text.chars().count(),
+        Value::Array(items) => items.iter().map(text_len).sum(),
+        Value::Object(map) => map
+            .iter()
+            // Image and file payloads are billed differently from their size.
+            .filter(|(key, _)| !matches!(key.as_str(), "image_url" | "file_data" | "data"))
+            .map(|(_, value)| text_len(value))
+            .sum(),
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v1::chat_completion::chat_completion::ChatCompletionRequest;
+    use crate::v1::chat_completion::{ChatCompletionMessage, Content, MessageRole};
+    use crate::v1::embedding::EmbeddingRequest;
+    use crate::v1::responses::responses::CreateResponseRequest;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_estimate_chat_completion_tokens() {
+        let req = ChatCompletionRequest::new(
+            "gpt-4o".to_string(),
+            vec![ChatCompletionMessage {
+                role: MessageRole::user,
+                content: Content::Text("a".repeat(40)),
+                name: None,
+                tool_calls: None,
+                tool_call_id: None,
+                audio: None,
+            }],
+        )
+        .max_tokens(100)
+        .n(2);
+        let body = serde_json::to_value(&req).unwrap();
+        // 40 chars of content + "user" role, one message of overhead, 2 x 100 output.
+        assert_eq!(estimate_tokens(&body), 11 + 4 + 200);
+    }
+
+    #[test]
+    fn test_estimate_embedding_tokens() {
+        let req = EmbeddingRequest::new(
+            "text-embedding-3-small".to_string(),
+            vec!["a".repeat(8), "b".repeat(8)],
+        );
+        let body = serde_json::to_value(&req).unwrap();
+        assert_eq!(estimate_tokens(&body), 4 + 8);
+    }
+
+    #[test]
+    fn test_estimate_response_tokens() {
+        let mut req = CreateResponseRequest::new();
+        req.model = Some("gpt-4.1".to_string());
+        req.input = Some("a".repeat(20).into());
+        req.instructions = Some("b".repeat(8));
+        req.max_output_tokens = Some(50);
+        let body = serde_json::to_value(&req).unwrap();
+        assert_eq!(estimate_tokens(&body), 7 + 50);
+    }
+
+    #[test]
+    fn test_bucket_waits_for_refill() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::per_minute(60.0, now);
+        assert_eq!(bucket.wait_time(60.0, now), None);
+        bucket.take(60.0);
+        assert_eq!(bucket.wait_time(1.0, now), Some(Duration::from_secs(1)));
+        assert_eq!(bucket.wait_time(1.0, now + Duration::from_secs(1)), None);
+        // Oversized requests are clamped to the capacity.
+        assert_eq!(
+            bucket.wait_time(1000.0, now + Duration::from_secs(1)),
+            Some(Duration::from_secs(59))
+        );
+    }
+
+    #[test]
+    fn test_headers_correct_bucket() {
+        let now = Instant::now();
+        let mut buckets = ModelBuckets {
+            requests: None,
+            tokens: Some(TokenBucket::per_minute(1000.0, now)),
+        };
+        buckets.update(
+            &RateLimitInfo {
+                limit_requests: Some(600),
+                remaining_requests: Some(0),
+                reset_requests: Some(Duration::from_secs(2)),
+                remaining_tokens: Some(10),
+                ..Default::default()
+            },
+            now,
+        );
+
+        let requests = buckets.requests.as_mut().unwrap();
+        assert_eq!(requests.capacity, 600.0);
+        assert_eq!(requests.wait_time(1.0, now), Some(Duration::from_secs(2)));
+        let tokens = buckets.tokens.as_mut().unwrap();
+        assert_eq!(tokens.available, 10.0);
+    }
+
+    #[tokio::test]
+    async fn test_acquire_blocks_when_budget_is_spent() {
+        let limiter = RateLimiter::new().with_model_limits(
+            "gpt-4o",
+            RateLimits {
+                requests_per_minute: Some(2),
+                tokens_per_minute: None,
+            },
+        );
+        limiter.acquire("gpt-4o", 10).await;
+        limiter.acquire("gpt-4o", 10).await;
+        let third = tokio::time::timeout(Duration::from_millis(50), limiter.acquire("gpt-4o", 10));
+        assert!(third.await.is_err());
+
+        // Other models are not limited.
+        limiter.acquire("gpt-4o-mini", 10).await;
+    }
+
+    #[tokio::test]
+    async fn test_learns_limits_from_headers() {
+        let limiter = RateLimiter::new();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-limit-requests", HeaderValue::from_static("60"));
+        headers.insert(
+            "x-ratelimit-remaining-requests",
+            HeaderValue::from_static("0"),
+        );
+        headers.insert("x-ratelimit-reset-requests", HeaderValue::from_static("1m"));
+        limiter.update_from_headers("gpt-4o", &headers);
+
+        let blocked = tokio::time::timeout(Duration::from_millis(50), limiter.acquire("gpt-4o", 1));
+        assert!(blocked.await.is_err());
+    }
+}
@@ -0,0 +1,120 @@
+use crate::v1::retry::RetryPolicy;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde_json::{Map, Value};
+use std::time::Duration;
+
+/// Per-call overrides, applied with `OpenAIClient::with_options`.
+///
+/// ```no_run
+/// # use openai_api_rs::v1::api::OpenAIClient;
+/// # use openai_api_rs::v1::request_options::RequestOptions;
+/// # use std::time::Duration;
+/// # async fn run(client: OpenAIClient) {
+/// let client = client.with_options(
+///     RequestOptions::new()
+///         .with_timeout(Duration::from_secs(10))
+///         .with_idempotency_key("batch-42"),
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions {
+    /// For regular calls, the time allowed for each attempt including the
+    /// response body. For streaming calls, the time allowed until the
+    /// response headers arrive.
+    pub timeout: Option<Duration>,
+    /// Longest gap allowed between two chunks of a streaming response
+    /// before the stream ends with `APIError::Timeout`.
+    pub stream_idle_timeout: Option<Duration>,
+    pub headers: HeaderMap,
+    pub query: Vec<(String, String)>,
+    /// Fields merged into JSON request bodies, or added as text fields to
+    /// multipart forms. Existing fields with the same name are replaced.
+    pub extra_body: Map<String, Value>,
+    /// Sent as the `Idempotency-Key` header of one write call and its
+    /// retries; see `OpenAIClient::with_options`. Not allowed in client
+    /// defaults.
+    pub idempotency_key: Option<String>,
+    pub retry_policy: Option<RetryPolicy>,
+}
+
+impl RequestOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_stream_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.stream_idle_timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(
+            HeaderName::from_bytes(key.into().as_bytes()).expect("Invalid header name"),
+            HeaderValue::from_str(&value.into()).expect("Invalid header value"),
+        );
+        self
+    }
+
+    pub fn with_query(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.query.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn with_extra_body(mut self, key: impl Into<String>, value: Value) -> Self {
+        self.extra_body.insert(key.into(), value);
+        self
+    }
+
+    pub fn with_idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.idempotency_key = Some(key.into());
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Layers `other` on top of these options. Values set in `other` win;
+    /// headers, query parameters and body fields are combined.
+    pub fn merge(mut self, other: RequestOptions) -> Self {
+        self.timeout = other.timeout.or(self.timeout);
+        self.stream_idle_timeout = other.stream_idle_timeout.or(self.stream_idle_timeout);
+        self.headers.extend(other.headers);
+        self.query.extend(other.query);
+        self.extra_body.extend(other.extra_body);
+        self.idempotency_key = other.idempotency_key.or(self.idempotency_key);
+        self.retry_policy = other.retry_policy.or(self.retry_policy);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge() {
+        let base = RequestOptions::new()
+            .with_timeout(Duration::from_secs(30))
+            .with_header("x-team", "search")
+            .with_extra_body("user", "a".into());
+        let merged = base.merge(
+            RequestOptions::new()
+                .with_header("x-trace", "1")
+                .with_extra_body("user", "b".into())
+                .with_idempotency_key("key-1"),
+        );
+
+        assert_eq!(merged.timeout, Some(Duration::from_secs(30)));
+        assert_eq!(merged.headers.len(), 2);
+        assert_eq!(merged.extra_body["user"], "b");
+        assert_eq!(merged.idempotency_key.as_deref(), Some("key-1"));
+    }
+}
@@ -1,8 +1,12 @@
-use crate::v1::types::Tools;
+use crate::v1::chat_completion::Reasoning;
+use crate::v1::pagination::{CursorPage, PageItem};
+use crate::v1::rate_limit::{RateLimitInfo, ResponseMeta};
+use crate::v1::types::{Function, Tools};
 use reqwest::header::HeaderMap;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::BTreeMap;
+use std::time::Duration;
 
 // pub mod responses_stream;
 
@@ -12,6 +16,25 @@ pub struct CallResponse<T> {
     pub inner: T,
 }
 
+impl<T> CallResponse<T> {
+    /// Rate-limit state parsed from the `x-ratelimit-*` headers.
+    pub fn rate_limit(&self) -> RateLimitInfo {
+        RateLimitInfo::from_headers(&self.headers)
+    }
+
+    /// Request id, model, processing time and rate limits parsed from the headers.
+    pub fn meta(&self) -> ResponseMeta {
+        ResponseMeta::from_headers(&self.headers)
+    }
+
+    /// Value of the `x-request-id` header.
+    pub fn request_id(&self) -> Option<&str> {
+        self.headers
+            .get("x-request-id")
+            .and_then(|value| value.to_str().ok())
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CreateResponseRequest {
     // background
@@ -20,7 +43,7 @@ pub struct CreateResponseRequest {
 
     // conversation
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub conversation: Option<Value>,
+    pub conversation: Option<ConversationRef>,
 
     // include
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -28,7 +51,7 @@ pub struct CreateResponseRequest {
 
     // input
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub input: Option<Value>,
+    pub input: Option<ResponseInput>,
 
     // instructions
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -68,7 +91,7 @@ pub struct CreateResponseRequest {
 
     // reasoning
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub reasoning: Option<Value>,
+    pub reasoning: Option<Reasoning>,
 
     // safety_identifier
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -96,15 +119,15 @@ pub struct CreateResponseRequest {
 
     // text
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub text: Option<Value>,
+    pub text: Option<TextConfig>,
 
     // tool_choice
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub tool_choice: Option<Value>,
+    pub tool_choice: Option<ResponseToolChoice>,
 
     // tools
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub tools: Option<Vec<Tools>>,
+    pub tools: Option<Vec<ResponseTool>>,
 
     // top_logprobs
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -182,10 +205,8 @@ pub struct ResponseObject {
     pub status: Option<String>,
 
     // Output
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub output: Option<Value>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub output_text: Option<String>,
+    #[serde(default)]
+    pub output: Vec<OutputItem>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub output_audio: Option<Value>,
 
@@ -198,12 +219,24 @@ pub struct ResponseObject {
     // Tools
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_calls: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<ResponseToolChoice>,
+
+    // Configuration echoed from the request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conversation: Option<ConversationRef>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_response_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<Reasoning>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<TextConfig>,
 
     // Misc
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub usage: Option<Value>,
+    pub usage: Option<ResponseUsage>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub system_fingerprint: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -222,15 +255,95 @@ pub struct ResponseObject {
     pub extra: BTreeMap<String, Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct ListResponses {
-    pub object: String,
-    pub data: Vec<ResponseObject>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub first_id: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub last_id: Option<String>,
-    pub has_more: bool,
+impl ResponseObject {
+    /// The text of all `output_text` parts in the output messages, joined
+    /// in order.
+    pub fn output_text(&self) -> String {
+        self.output
+            .iter()
+            .filter_map(|item| match item {
+                OutputItem::Message(message) => Some(message),
+                _ => None,
+            })
+            .flat_map(|message| &message.content)
+            .filter_map(|content| match content {
+                OutputContent::OutputText(text) => Some(text.text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The function calls the model asked for.
+    pub fn function_calls(&self) -> impl Iterator<Item = &FunctionCallItem> {
+        self.output.iter().filter_map(|item| match item {
+            OutputItem::FunctionCall(call) => Some(call),
+            _ => None,
+        })
+    }
+}
+
+impl ResponseObject {
+    /// Whether the response has stopped running: `completed`, `failed`,
+    /// `cancelled` or `incomplete`.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self.status.as_deref(),
+            Some("completed" | "failed" | "cancelled" | "incomplete")
+        )
+    }
+}
+
+/// How `OpenAIClient::wait_for_response` polls a background response.
+///
+/// The delay between polls starts at `interval` and doubles up to
+/// `max_interval`. Polling gives up with `APIError::Timeout` after `timeout`.
+#[derive(Debug, Clone)]
+pub struct PollOptions {
+    pub interval: Duration,
+    pub max_interval: Duration,
+    pub timeout: Option<Duration>,
+}
+
+impl Default for PollOptions {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(1),
+            max_interval: Duration::from_secs(30),
+            timeout: None,
+        }
+    }
+}
+
+impl PollOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn with_max_interval(mut self, max_interval: Duration) -> Self {
+        self.max_interval = max_interval;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
+pub type ListResponses = CursorPage<ResponseObject>;
+
+/// Page of `list_response_input_items`; items come back in their input shape.
+pub type ResponseInputItemList = CursorPage<InputItem>;
+
+impl PageItem for ResponseObject {
+    fn page_id(&self) -> &str {
+        &self.id
+    }
 }
 
 // Get input token counts (POST /v1/responses/input_tokens)
@@ -238,11 +351,11 @@ pub struct ListResponses {
 pub struct CountTokensRequest {
     // conversation
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub conversation: Option<Value>,
+    pub conversation: Option<ConversationRef>,
 
     // input
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub input: Option<Value>,
+    pub input: Option<ResponseInput>,
 
     // instructions
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -262,19 +375,19 @@ pub struct CountTokensRequest {
 
     // reasoning
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub reasoning: Option<Value>,
+    pub reasoning: Option<Reasoning>,
 
     // text
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub text: Option<Value>,
+    pub text: Option<TextConfig>,
 
     // tool_choice
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub tool_choice: Option<Value>,
+    pub tool_choice: Option<ResponseToolChoice>,
 
     // tools
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub tools: Option<Vec<Tools>>,
+    pub tools: Option<Vec<ResponseTool>>,
 
     // truncation
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -319,3 +432,605 @@ pub struct CountTokensResponse {
     #[serde(flatten)]
     pub extra: BTreeMap<String, Value>,
 }
+
+/// A conversation the response belongs to, given by id or as `{"id": ...}`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum ConversationRef {
+    Id(String),
+    Object { id: String },
+}
+
+impl ConversationRef {
+    pub fn id(&self) -> &str {
+        match self {
+            ConversationRef::Id(id) | ConversationRef::Object { id } => id,
+        }
+    }
+}
+
+impl From<&str> for ConversationRef {
+    fn from(id: &str) -> Self {
+        ConversationRef::Id(id.to_string())
+    }
+}
+
+impl From<String> for ConversationRef {
+    fn from(id: String) -> Self {
+        ConversationRef::Id(id)
+    }
+}
+
+/// Text output options.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct TextConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<TextFormat>,
+    /// `low`, `medium` or `high`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verbosity: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TextFormat {
+    Text,
+    JsonObject,
+    JsonSchema {
+        name: String,
+        schema: Value,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        strict: Option<bool>,
+    },
+    /// A format this version does not know about yet.
+    #[serde(untagged)]
+    Other(Value),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolChoiceMode {
+    None,
+    Auto,
+    Required,
+}
+
+/// A tool the model may call. Function tools carry their fields at the top
+/// level, unlike the nested Chat Completions and Assistants shape.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseTool {
+    Function(Function),
+    #[serde(untagged)]
+    Other(Value),
+}
+
+impl From<Function> for ResponseTool {
+    fn from(function: Function) -> Self {
+        ResponseTool::Function(function)
+    }
+}
+
+impl From<Tools> for ResponseTool {
+    fn from(tool: Tools) -> Self {
+        match tool {
+            Tools::Function(tool) => ResponseTool::Function(tool.function),
+            other => match serde_json::to_value(other) {
+                Ok(value) => ResponseTool::Other(value),
+                Err(_) => ResponseTool::Other(Value::Null),
+            },
+        }
+    }
+}
+
+/// How the model picks tools: a mode, or one specific tool.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum ResponseToolChoice {
+    Mode(ToolChoiceMode),
+    Tool(NamedToolChoice),
+}
+
+impl ResponseToolChoice {
+    pub fn function(name: impl Into<String>) -> Self {
+        ResponseToolChoice::Tool(NamedToolChoice::Function { name: name.into() })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NamedToolChoice {
+    Function {
+        name: String,
+    },
+    FileSearch,
+    WebSearchPreview,
+    ComputerUsePreview,
+    ImageGeneration,
+    CodeInterpreter,
+    Mcp {
+        server_label: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+    #[serde(untagged)]
+    Other(Value),
+}
+
+/// The `input` of a response: plain text, or a list of items.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum ResponseInput {
+    Text(String),
+    Items(Vec<InputItem>),
+}
+
+impl From<&str> for ResponseInput {
+    fn from(text: &str) -> Self {
+        ResponseInput::Text(text.to_string())
+    }
+}
+
+impl From<String> for ResponseInput {
+    fn from(text: String) -> Self {
+        ResponseInput::Text(text)
+    }
+}
+
+impl From<Vec<InputItem>> for ResponseInput {
+    fn from(items: Vec<InputItem>) -> Self {
+        ResponseInput::Items(items)
+    }
+}
+
+/// One item of a response's input. Output items from an earlier response
+/// can be passed back with `From<OutputItem>`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InputItem {
+    Message(InputMessage),
+    FunctionCall(FunctionCallItem),
+    FunctionCallOutput {
+        call_id: String,
+        output: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+    },
+    Reasoning(ReasoningItem),
+    ItemReference {
+        id: String,
+    },
+    #[serde(untagged)]
+    Other(Value),
+}
+
+impl InputItem {
+    pub fn message(role: InputRole, content: impl Into<InputContent>) -> Self {
+        InputItem::Message(InputMessage {
+            role,
+            content: content.into(),
+            id: None,
+            status: None,
+        })
+    }
+
+    pub fn user(content: impl Into<InputContent>) -> Self {
+        Self::message(InputRole::User, content)
+    }
+
+    pub fn system(content: impl Into<InputContent>) -> Self {
+        Self::message(InputRole::System, content)
+    }
+
+    pub fn developer(content: impl Into<InputContent>) -> Self {
+        Self::message(InputRole::Developer, content)
+    }
+
+    pub fn assistant(content: impl Into<InputContent>) -> Self {
+        Self::message(InputRole::Assistant, content)
+    }
+
+    pub fn function_call_output(call_id: impl Into<String>, output: impl Into<String>) -> Self {
+        InputItem::FunctionCallOutput {
+            call_id: call_id.into(),
+            output: output.into(),
+            id: None,
+        }
+    }
+
+    pub fn item_reference(id: impl Into<String>) -> Self {
+        InputItem::ItemReference { id: id.into() }
+    }
+}
+
+impl From<OutputItem> for InputItem {
+    fn from(item: OutputItem) -> Self {
+        match item {
+            OutputItem::FunctionCall(call) => InputItem::FunctionCall(call),
+            OutputItem::Reasoning(reasoning) => InputItem::Reasoning(reasoning),
+            other => match serde_json::to_value(other) {
+                Ok(value) => InputItem::Other(value),
+                Err(_) => InputItem::Other(Value::Null),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum InputRole {
+    User,
+    Assistant,
+    System,
+    Developer,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct InputMessage {
+    pub role: InputRole,
+    pub content: InputContent,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum InputContent {
+    Text(String),
+    Parts(Vec<InputContentPart>),
+}
+
+impl From<&str> for InputContent {
+    fn from(text: &str) -> Self {
+        InputContent::Text(text.to_string())
+    }
+}
+
+impl From<String> for InputContent {
+    fn from(text: String) -> Self {
+        InputContent::Text(text)
+    }
+}
+
+impl From<Vec<InputContentPart>> for InputContent {
+    fn from(parts: Vec<InputContentPart>) -> Self {
+        InputContent::Parts(parts)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InputContentPart {
+    InputText {
+        text: String,
+    },
+    InputImage {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        image_url: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        file_id: Option<String>,
+        /// `low`, `high` or `auto`.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        detail: Option<String>,
+    },
+    InputFile {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        file_id: Option<String>,
+        /// Base64 data URL of the file.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        file_data: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        file_url: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        filename: Option<String>,
+    },
+    /// Text of an earlier assistant message.
+    OutputText {
+        text: String,
+    },
+    Refusal {
+        refusal: String,
+    },
+    #[serde(untagged)]
+    Other(Value),
+}
+
+impl InputContentPart {
+    pub fn text(text: impl Into<String>) -> Self {
+        InputContentPart::InputText { text: text.into() }
+    }
+
+    /// An image by URL, which may be a base64 data URL.
+    pub fn image_url(url: impl Into<String>) -> Self {
+        InputContentPart::InputImage {
+            image_url: Some(url.into()),
+            file_id: None,
+            detail: None,
+        }
+    }
+
+    pub fn image_file(file_id: impl Into<String>) -> Self {
+        InputContentPart::InputImage {
+            image_url: None,
+            file_id: Some(file_id.into()),
+            detail: None,
+        }
+    }
+
+    pub fn file(file_id: impl Into<String>) -> Self {
+        InputContentPart::InputFile {
+            file_id: Some(file_id.into()),
+            file_data: None,
+            file_url: None,
+            filename: None,
+        }
+    }
+}
+
+/// One item of a response's output.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OutputItem {
+    Message(OutputMessage),
+    Reasoning(ReasoningItem),
+    FunctionCall(FunctionCallItem),
+    WebSearchCall(WebSearchCall),
+    FileSearchCall(FileSearchCall),
+    ComputerCall(ComputerCall),
+    ImageGenerationCall(ImageGenerationCall),
+    /// Item types this crate does not model yet.
+    #[serde(untagged)]
+    Other(Value),
+}
+
+impl OutputItem {
+    pub fn id(&self) -> Option<&str> {
+        match self {
+            OutputItem::Message(item) => Some(&item.id),
+            OutputItem::Reasoning(item) => Some(&item.id),
+            OutputItem::FunctionCall(item) => item.id.as_deref(),
+            OutputItem::WebSearchCall(item) => Some(&item.id),
+            OutputItem::FileSearchCall(item) => Some(&item.id),
+            OutputItem::ComputerCall(item) => Some(&item.id),
+            OutputItem::ImageGenerationCall(item) => Some(&item.id),
+            OutputItem::Other(value) => value.get("id").and_then(Value::as_str),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct OutputMessage {
+    pub id: String,
+    #[serde(default = "assistant_role")]
+    pub role: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub content: Vec<OutputContent>,
+}
+
+fn assistant_role() -> String {
+    "assistant".to_string()
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OutputContent {
+    OutputText(OutputText),
+    Refusal {
+        refusal: String,
+    },
+    #[serde(untagged)]
+    Other(Value),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct OutputText {
+    pub text: String,
+    #[serde(default)]
+    pub annotations: Vec<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logprobs: Option<Vec<Value>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReasoningItem {
+    pub id: String,
+    #[serde(default)]
+    pub summary: Vec<ReasoningText>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<Vec<ReasoningText>>,
+    /// Present when `reasoning.encrypted_content` is included, so the
+    /// reasoning can be passed back without storing the response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encrypted_content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+}
+
+/// A `summary_text` or `reasoning_text` part.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReasoningText {
+    pub r#type: String,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FunctionCallItem {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub call_id: String,
+    pub name: String,
+    /// JSON-encoded arguments.
+    pub arguments: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WebSearchCall {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FileSearchCall {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub queries: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub results: Option<Vec<Value>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ComputerCall {
+    pub id: String,
+    pub call_id: String,
+    pub action: Value,
+    #[serde(default)]
+    pub pending_safety_checks: Vec<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ImageGenerationCall {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// The generated image, base64-encoded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revised_prompt: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct ResponseUsage {
+    #[serde(default)]
+    pub input_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_tokens_details: Option<InputTokensDetails>,
+    #[serde(default)]
+    pub output_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_tokens_details: Option<OutputTokensDetails>,
+    #[serde(default)]
+    pub total_tokens: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct InputTokensDetails {
+    #[serde(default)]
+    pub cached_tokens: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct OutputTokensDetails {
+    #[serde(default)]
+    pub reasoning_tokens: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_input_serialization() {
+        let mut req = CreateResponseRequest::new();
+        req.input = Some(ResponseInput::Items(vec![
+            InputItem::developer("Be brief."),
+            InputItem::user(vec![
+                InputContentPart::text("What is in this image?"),
+                InputContentPart::image_url("https://example.com/cat.png"),
+            ]),
+            InputItem::function_call_output("call_1", "{\"ok\":true}"),
+            InputItem::item_reference("msg_1"),
+        ]));
+        req.tool_choice = Some(ResponseToolChoice::function("get_weather"));
+        req.conversation = Some("conv_1".into());
+
+        let body = serde_json::to_value(&req).unwrap();
+        assert_eq!(
+            body["input"],
+            json!([
+                {"type": "message", "role": "developer", "content": "Be brief."},
+                {"type": "message", "role": "user", "content": [
+                    {"type": "input_text", "text": "What is in this image?"},
+                    {"type": "input_image", "image_url": "https://example.com/cat.png"}
+                ]},
+                {"type": "function_call_output", "call_id": "call_1", "output": "{\"ok\":true}"},
+                {"type": "item_reference", "id": "msg_1"}
+            ])
+        );
+        assert_eq!(
+            body["tool_choice"],
+            json!({"type": "function", "name": "get_weather"})
+        );
+        assert_eq!(body["conversation"], "conv_1");
+    }
+
+    #[test]
+    fn test_output_deserialization() {
+        let resp: ResponseObject = serde_json::from_value(json!({
+            "id": "resp_1",
+            "object": "response",
+            "status": "completed",
+            "output": [
+                {"type": "reasoning", "id": "rs_1", "summary": [{"type": "summary_text", "text": "Hmm"}]},
+                {"type": "function_call", "id": "fc_1", "call_id": "call_1",
+                 "name": "get_weather", "arguments": "{}", "status": "completed"},
+                {"type": "message", "id": "msg_1", "role": "assistant", "status": "completed",
+                 "content": [
+                    {"type": "output_text", "text": "Hello, ", "annotations": []},
+                    {"type": "refusal", "refusal": "no"},
+                    {"type": "output_text", "text": "world"}
+                 ]},
+                {"type": "mcp_list_tools", "id": "mcpl_1", "tools": []}
+            ],
+            "tool_choice": "auto",
+            "usage": {
+                "input_tokens": 10,
+                "input_tokens_details": {"cached_tokens": 4},
+                "output_tokens": 20,
+                "output_tokens_details": {"reasoning_tokens": 12},
+                "total_tokens": 30
+            },
+            "text": {"format": {"type": "grammar", "syntax": "lark"}},
+            "truncation": "disabled"
+        }))
+        .unwrap();
+
+        assert_eq!(resp.output_text(), "Hello, world");
+        assert_eq!(resp.function_calls().next().unwrap().name, "get_weather");
+        assert!(matches!(resp.output[3], OutputItem::Other(_)));
+        assert_eq!(resp.output[3].id(), Some("mcpl_1"));
+        assert_eq!(
+            resp.tool_choice,
+            Some(ResponseToolChoice::Mode(ToolChoiceMode::Auto))
+        );
+        let usage = resp.usage.unwrap();
+        assert_eq!(usage.input_tokens_details.unwrap().cached_tokens, 4);
+        assert_eq!(usage.output_tokens_details.unwrap().reasoning_tokens, 12);
+        assert_eq!(resp.extra["truncation"], "disabled");
+        assert_eq!(
+            resp.text.as_ref().unwrap().format,
+            Some(TextFormat::Other(
+                json!({"type": "grammar", "syntax": "lark"})
+            ))
+        );
+
+        let next: InputItem = resp.output[1].clone().into();
+        assert_eq!(serde_json::to_value(next).unwrap()["type"], "function_call");
+    }
+}
@@ -1,15 +1,313 @@
-use super::responses::CreateResponseRequest;
+use super::responses::{
+    CreateResponseRequest, OutputContent, OutputItem, ReasoningText, ResponseObject,
+};
+use crate::v1::api::ByteStream;
+use crate::v1::error::APIError;
+use crate::v1::sse::{SseEvent, SseStream};
 use futures_util::Stream;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::ops::Range;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
 pub type CreateResponseStreamRequest = CreateResponseRequest;
 
-#[derive(Debug, Clone)]
-pub struct ResponseStreamEvent {
-    pub event: Option<String>,
-    pub data: Value,
+/// One event of a streamed response, identified by its `type`.
+///
+/// Event types this crate does not model yet, or events whose payload does
+/// not match the documented shape, are kept as `Unknown` with the raw data.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ResponseStreamEvent {
+    #[serde(rename = "response.created")]
+    Created(ResponseEvent),
+    #[serde(rename = "response.queued")]
+    Queued(ResponseEvent),
+    #[serde(rename = "response.in_progress")]
+    InProgress(ResponseEvent),
+    #[serde(rename = "response.completed")]
+    Completed(ResponseEvent),
+    #[serde(rename = "response.failed")]
+    Failed(ResponseEvent),
+    #[serde(rename = "response.incomplete")]
+    Incomplete(ResponseEvent),
+    #[serde(rename = "response.output_item.added")]
+    OutputItemAdded(OutputItemEvent),
+    #[serde(rename = "response.output_item.done")]
+    OutputItemDone(OutputItemEvent),
+    #[serde(rename = "response.content_part.added")]
+    ContentPartAdded(ContentPartEvent),
+    #[serde(rename = "response.content_part.done")]
+    ContentPartDone(ContentPartEvent),
+    #[serde(rename = "response.output_text.delta")]
+    OutputTextDelta(TextDeltaEvent),
+    #[serde(rename = "response.output_text.done")]
+    OutputTextDone(TextDoneEvent),
+    #[serde(rename = "response.refusal.delta")]
+    RefusalDelta(TextDeltaEvent),
+    #[serde(rename = "response.refusal.done")]
+    RefusalDone(RefusalDoneEvent),
+    #[serde(rename = "response.reasoning_summary_part.added")]
+    ReasoningSummaryPartAdded(ReasoningSummaryPartEvent),
+    #[serde(rename = "response.reasoning_summary_part.done")]
+    ReasoningSummaryPartDone(ReasoningSummaryPartEvent),
+    #[serde(rename = "response.reasoning_summary_text.delta")]
+    ReasoningSummaryTextDelta(ReasoningSummaryDeltaEvent),
+    #[serde(rename = "response.reasoning_summary_text.done")]
+    ReasoningSummaryTextDone(ReasoningSummaryDoneEvent),
+    #[serde(rename = "response.reasoning_text.delta")]
+    ReasoningTextDelta(TextDeltaEvent),
+    #[serde(rename = "response.reasoning_text.done")]
+    ReasoningTextDone(TextDoneEvent),
+    #[serde(rename = "response.function_call_arguments.delta")]
+    FunctionCallArgumentsDelta(FunctionCallArgumentsDeltaEvent),
+    #[serde(rename = "response.function_call_arguments.done")]
+    FunctionCallArgumentsDone(FunctionCallArgumentsDoneEvent),
+    #[serde(rename = "response.web_search_call.in_progress")]
+    WebSearchCallInProgress(ToolCallEvent),
+    #[serde(rename = "response.web_search_call.searching")]
+    WebSearchCallSearching(ToolCallEvent),
+    #[serde(rename = "response.web_search_call.completed")]
+    WebSearchCallCompleted(ToolCallEvent),
+    #[serde(rename = "response.file_search_call.in_progress")]
+    FileSearchCallInProgress(ToolCallEvent),
+    #[serde(rename = "response.file_search_call.searching")]
+    FileSearchCallSearching(ToolCallEvent),
+    #[serde(rename = "response.file_search_call.completed")]
+    FileSearchCallCompleted(ToolCallEvent),
+    #[serde(rename = "response.image_generation_call.in_progress")]
+    ImageGenerationCallInProgress(ToolCallEvent),
+    #[serde(rename = "response.image_generation_call.generating")]
+    ImageGenerationCallGenerating(ToolCallEvent),
+    #[serde(rename = "response.image_generation_call.completed")]
+    ImageGenerationCallCompleted(ToolCallEvent),
+    #[serde(rename = "response.image_generation_call.partial_image")]
+    ImageGenerationCallPartialImage(PartialImageEvent),
+    #[serde(rename = "response.code_interpreter_call.in_progress")]
+    CodeInterpreterCallInProgress(ToolCallEvent),
+    #[serde(rename = "response.code_interpreter_call.interpreting")]
+    CodeInterpreterCallInterpreting(ToolCallEvent),
+    #[serde(rename = "response.code_interpreter_call.completed")]
+    CodeInterpreterCallCompleted(ToolCallEvent),
+    #[serde(rename = "response.mcp_call.in_progress")]
+    McpCallInProgress(ToolCallEvent),
+    #[serde(rename = "response.mcp_call.completed")]
+    McpCallCompleted(ToolCallEvent),
+    #[serde(rename = "response.mcp_call.failed")]
+    McpCallFailed(ToolCallEvent),
+    #[serde(rename = "error")]
+    Error(ErrorEvent),
+    #[serde(untagged)]
+    Unknown(Value),
+}
+
+impl ResponseStreamEvent {
+    /// The event's position in the stream, starting at 0.
+    pub fn sequence_number(&self) -> Option<u64> {
+        use ResponseStreamEvent::*;
+        match self {
+            Created(e) | Queued(e) | InProgress(e) | Completed(e) | Failed(e) | Incomplete(e) => {
+                e.sequence_number
+            }
+            OutputItemAdded(e) | OutputItemDone(e) => e.sequence_number,
+            ContentPartAdded(e) | ContentPartDone(e) => e.sequence_number,
+            OutputTextDelta(e) | RefusalDelta(e) | ReasoningTextDelta(e) => e.sequence_number,
+            OutputTextDone(e) | ReasoningTextDone(e) => e.sequence_number,
+            RefusalDone(e) => e.sequence_number,
+            ReasoningSummaryPartAdded(e) | ReasoningSummaryPartDone(e) => e.sequence_number,
+            ReasoningSummaryTextDelta(e) => e.sequence_number,
+            ReasoningSummaryTextDone(e) => e.sequence_number,
+            FunctionCallArgumentsDelta(e) => e.sequence_number,
+            FunctionCallArgumentsDone(e) => e.sequence_number,
+            WebSearchCallInProgress(e)
+            | WebSearchCallSearching(e)
+            | WebSearchCallCompleted(e)
+            | FileSearchCallInProgress(e)
+            | FileSearchCallSearching(e)
+            | FileSearchCallCompleted(e)
+            | ImageGenerationCallInProgress(e)
+            | ImageGenerationCallGenerating(e)
+            | ImageGenerationCallCompleted(e)
+            | CodeInterpreterCallInProgress(e)
+            | CodeInterpreterCallInterpreting(e)
+            | CodeInterpreterCallCompleted(e)
+            | McpCallInProgress(e)
+            | McpCallCompleted(e)
+            | McpCallFailed(e) => e.sequence_number,
+            ImageGenerationCallPartialImage(e) => e.sequence_number,
+            Error(e) => e.sequence_number,
+            Unknown(data) => data.get("sequence_number").and_then(Value::as_u64),
+        }
+    }
+
+    /// The text of an `output_text.delta` event.
+    pub fn text_delta(&self) -> Option<&str> {
+        match self {
+            ResponseStreamEvent::OutputTextDelta(e) => Some(&e.delta),
+            _ => None,
+        }
+    }
+
+    /// The response carried by lifecycle events such as `response.completed`.
+    pub fn response(&self) -> Option<&ResponseObject> {
+        use ResponseStreamEvent::*;
+        match self {
+            Created(e) | Queued(e) | InProgress(e) | Completed(e) | Failed(e) | Incomplete(e) => {
+                Some(&e.response)
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether this is the last lifecycle event of a stream:
+    /// `response.completed`, `response.failed` or `response.incomplete`.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            ResponseStreamEvent::Completed(_)
+                | ResponseStreamEvent::Failed(_)
+                | ResponseStreamEvent::Incomplete(_)
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResponseEvent {
+    pub response: Box<ResponseObject>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutputItemEvent {
+    pub output_index: u32,
+    pub item: OutputItem,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentPartEvent {
+    pub item_id: String,
+    pub output_index: u32,
+    pub content_index: u32,
+    pub part: OutputContent,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextDeltaEvent {
+    pub item_id: String,
+    pub output_index: u32,
+    #[serde(default)]
+    pub content_index: u32,
+    pub delta: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextDoneEvent {
+    pub item_id: String,
+    pub output_index: u32,
+    #[serde(default)]
+    pub content_index: u32,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefusalDoneEvent {
+    pub item_id: String,
+    pub output_index: u32,
+    pub content_index: u32,
+    pub refusal: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReasoningSummaryPartEvent {
+    pub item_id: String,
+    pub output_index: u32,
+    pub summary_index: u32,
+    pub part: ReasoningText,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReasoningSummaryDeltaEvent {
+    pub item_id: String,
+    pub output_index: u32,
+    pub summary_index: u32,
+    pub delta: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReasoningSummaryDoneEvent {
+    pub item_id: String,
+    pub output_index: u32,
+    pub summary_index: u32,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionCallArgumentsDeltaEvent {
+    pub item_id: String,
+    pub output_index: u32,
+    pub delta: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionCallArgumentsDoneEvent {
+    pub item_id: String,
+    pub output_index: u32,
+    pub arguments: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+}
+
+/// Progress of a hosted tool call, such as a web search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolCallEvent {
+    pub item_id: String,
+    pub output_index: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartialImageEvent {
+    pub item_id: String,
+    pub output_index: u32,
+    pub partial_image_index: u32,
+    /// The partial image, base64-encoded.
+    pub partial_image_b64: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorEvent {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub param: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
 }
 
 #[derive(Debug, Clone)]
@@ -18,115 +316,216 @@ pub enum ResponseStreamResponse {
     Done,
 }
 
-pub struct ResponseStream<S: Stream<Item = Result<bytes::Bytes, reqwest::Error>> + Unpin> {
-    pub response: S,
-    pub buffer: String,
-    pub first_chunk: bool,
+/// Server-sent events of a streamed response.
+///
+/// Yields an error for transport failures, unparsable events and `error`
+/// events sent by the server. `is_done` tells whether the stream reached a
+/// terminal event, and `gaps` lists sequence numbers that were skipped.
+pub struct ResponseStream<S = ByteStream>
+where
+    S: Stream<Item = Result<bytes::Bytes, APIError>> + Unpin,
+{
+    events: SseStream<S>,
+    done: bool,
+    last_sequence_number: Option<u64>,
+    gaps: Vec<Range<u64>>,
 }
 
 impl<S> ResponseStream<S>
 where
-    S: Stream<Item = Result<bytes::Bytes, reqwest::Error>> + Unpin,
+    S: Stream<Item = Result<bytes::Bytes, APIError>> + Unpin,
 {
-    fn find_event_delimiter(buffer: &str) -> Option<(usize, usize)> {
-        let carriage_idx = buffer.find("\r\n\r\n");
-        let newline_idx = buffer.find("\n\n");
-
-        match (carriage_idx, newline_idx) {
-            (Some(r_idx), Some(n_idx)) => {
-                if r_idx <= n_idx {
-                    Some((r_idx, 4))
-                } else {
-                    Some((n_idx, 2))
-                }
-            }
-            (Some(r_idx), None) => Some((r_idx, 4)),
-            (None, Some(n_idx)) => Some((n_idx, 2)),
-            (None, None) => None,
+    pub fn new(response: S) -> Self {
+        Self {
+            events: SseStream::new(response),
+            done: false,
+            last_sequence_number: None,
+            gaps: Vec::new(),
         }
     }
 
-    fn next_response_from_buffer(&mut self) -> Option<ResponseStreamResponse> {
-        while let Some((idx, delimiter_len)) = Self::find_event_delimiter(&self.buffer) {
-            let event_block = self.buffer[..idx].to_owned();
-            self.buffer = self.buffer[idx + delimiter_len..].to_owned();
+    /// Continues a stream whose events up to `sequence_number` were already
+    /// received, so gaps are measured from there.
+    pub fn starting_after(mut self, sequence_number: u64) -> Self {
+        self.last_sequence_number = Some(sequence_number);
+        self
+    }
 
-            let mut event_name = None;
-            let mut data_payload = String::new();
+    /// Whether the response reached `response.completed`,
+    /// `response.failed` or `response.incomplete` (or a `[DONE]` message,
+    /// which some compatible servers send). A stream that ends before that
+    /// was cut short.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
 
-            for line in event_block.lines() {
-                let trimmed_line = line.trim_end_matches('\r');
+    /// Sequence number of the last event received.
+    pub fn last_sequence_number(&self) -> Option<u64> {
+        self.last_sequence_number
+    }
 
-                if let Some(event) = trimmed_line
-                    .strip_prefix("event: ")
-                    .or_else(|| trimmed_line.strip_prefix("event:"))
-                {
-                    let name = event.trim();
-                    if !name.is_empty() {
-                        event_name = Some(name.to_string());
-                    }
-                } else if let Some(content) = trimmed_line
-                    .strip_prefix("data: ")
-                    .or_else(|| trimmed_line.strip_prefix("data:"))
-                {
-                    if !content.is_empty() {
-                        if !data_payload.is_empty() {
-                            data_payload.push('\n');
-                        }
-                        data_payload.push_str(content);
-                    }
-                }
-            }
+    /// Ranges of sequence numbers that never arrived.
+    pub fn gaps(&self) -> &[Range<u64>] {
+        &self.gaps
+    }
 
-            if data_payload.is_empty() {
-                continue;
-            }
+    fn track_sequence_number(&mut self, sequence_number: u64) {
+        let expected = self.last_sequence_number.map_or(0, |last| last + 1);
+        if sequence_number > expected {
+            self.gaps.push(expected..sequence_number);
+        }
+        if self.last_sequence_number < Some(sequence_number) {
+            self.last_sequence_number = Some(sequence_number);
+        }
+    }
 
-            if data_payload.trim() == "[DONE]" {
-                return Some(ResponseStreamResponse::Done);
-            }
+    /// Interprets one event; `None` for events that carry nothing to yield.
+    fn parse_event(&mut self, event: SseEvent) -> Option<Result<ResponseStreamResponse, APIError>> {
+        if event.data.is_empty() {
+            return None;
+        }
+
+        if event.data.trim() == "[DONE]" {
+            self.done = true;
+            return Some(Ok(ResponseStreamResponse::Done));
+        }
 
-            let parsed = serde_json::from_str::<Value>(&data_payload)
-                .unwrap_or_else(|_| Value::String(data_payload.clone()));
+        let mut data = match serde_json::from_str::<Value>(&event.data) {
+            Ok(data) => data,
+            Err(source) => {
+                return Some(Err(APIError::DeserializeError {
+                    source,
+                    body: event.data,
+                }))
+            }
+        };
 
-            return Some(ResponseStreamResponse::Event(ResponseStreamEvent {
-                event: event_name,
-                data: parsed,
+        if event.event.as_deref() == Some("error")
+            || data.get("type").and_then(Value::as_str) == Some("error")
+        {
+            return Some(Err(APIError::StreamError {
+                message: data
+                    .get("message")
+                    .or_else(|| data.pointer("/error/message"))
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| data.to_string()),
             }));
         }
 
-        None
+        if let (Some(name), Some(object)) = (event.event, data.as_object_mut()) {
+            object.entry("type").or_insert(Value::String(name));
+        }
+        let event =
+            serde_json::from_value(data.clone()).unwrap_or(ResponseStreamEvent::Unknown(data));
+        if let Some(sequence_number) = event.sequence_number() {
+            self.track_sequence_number(sequence_number);
+        }
+        if event.is_terminal() {
+            self.done = true;
+        }
+        Some(Ok(ResponseStreamResponse::Event(event)))
     }
 }
 
-impl<S: Stream<Item = Result<bytes::Bytes, reqwest::Error>> + Unpin> Stream for ResponseStream<S> {
-    type Item = ResponseStreamResponse;
+impl<S: Stream<Item = Result<bytes::Bytes, APIError>> + Unpin> Stream for ResponseStream<S> {
+    type Item = Result<ResponseStreamResponse, APIError>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         loop {
-            if let Some(response) = self.next_response_from_buffer() {
-                return Poll::Ready(Some(response));
-            }
-
-            match Pin::new(&mut self.as_mut().response).poll_next(cx) {
-                Poll::Ready(Some(Ok(chunk))) => {
-                    let chunk_str = String::from_utf8_lossy(&chunk).to_string();
-                    if self.first_chunk {
-                        self.first_chunk = false;
+            match Pin::new(&mut self.events).poll_next(cx) {
+                Poll::Ready(Some(Ok(event))) => {
+                    if let Some(response) = self.parse_event(event) {
+                        return Poll::Ready(Some(response));
                     }
-                    self.buffer.push_str(&chunk_str);
                 }
-                Poll::Ready(Some(Err(error))) => {
-                    eprintln!("Error in stream: {:?}", error);
-                    return Poll::Ready(None);
-                }
-                Poll::Ready(None) => {
-                    return Poll::Ready(None);
-                }
-                Poll::Pending => {
-                    return Poll::Pending;
+                Poll::Ready(Some(Err(error))) => return Poll::Ready(Some(Err(error))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+
+    #[tokio::test]
+    async fn test_stream_surfaces_error_events() {
+        let chunks: Vec<Result<bytes::Bytes, APIError>> = vec![
+            Ok("event: response.output_text.delta\ndata: {\"item_id\":\"msg_1\",\"output_index\":0,\"delta\":\"Hi\"}\n\n".into()),
+            Ok("event: error\ndata: {\"type\":\"error\",\"code\":\"server_error\",\"message\":\"boom\"}\n\n".into()),
+        ];
+        let mut stream = ResponseStream::new(futures_util::stream::iter(chunks));
+
+        match stream.next().await {
+            Some(Ok(ResponseStreamResponse::Event(event))) => {
+                assert_eq!(event.text_delta(), Some("Hi"));
+            }
+            other => panic!("unexpected item: {other:?}"),
+        }
+        assert!(matches!(
+            stream.next().await,
+            Some(Err(APIError::StreamError { message })) if message == "boom"
+        ));
+        assert!(stream.next().await.is_none());
+        assert!(!stream.is_done());
+        // Neither event carried a sequence number.
+        assert_eq!(stream.last_sequence_number(), None);
+        assert!(stream.gaps().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_typed_events_and_sequence_gaps() {
+        let events = [
+            r#"{"type":"response.created","sequence_number":0,"response":{"id":"resp_1","object":"response","status":"in_progress","output":[]}}"#,
+            r#"{"type":"response.output_item.added","sequence_number":1,"output_index":0,"item":{"type":"function_call","id":"fc_1","call_id":"call_1","name":"lookup","arguments":""}}"#,
+            r#"{"type":"response.function_call_arguments.delta","sequence_number":2,"item_id":"fc_1","output_index":0,"delta":"{}"}"#,
+            r#"{"type":"response.reasoning_summary_text.delta","sequence_number":5,"item_id":"rs_1","output_index":1,"summary_index":0,"delta":"Hm"}"#,
+            r#"{"type":"response.brand_new","sequence_number":6,"foo":1}"#,
+            r#"{"type":"response.completed","sequence_number":7,"response":{"id":"resp_1","object":"response","status":"completed"}}"#,
+        ];
+        let chunks: Vec<Result<bytes::Bytes, APIError>> = events
+            .iter()
+            .map(|data| Ok(format!("data: {data}\n\n").into()))
+            .collect();
+        let stream = ResponseStream::new(futures_util::stream::iter(chunks));
+        let mut stream = std::pin::pin!(stream);
+
+        let mut received = vec![];
+        while let Some(item) = stream.next().await {
+            match item.unwrap() {
+                ResponseStreamResponse::Event(event) => {
+                    assert_eq!(stream.is_done(), event.is_terminal());
+                    received.push(event);
                 }
+                ResponseStreamResponse::Done => panic!("no [DONE] was sent"),
             }
         }
+
+        assert!(
+            matches!(&received[0], ResponseStreamEvent::Created(e) if e.response.id == "resp_1")
+        );
+        assert!(matches!(
+            &received[1],
+            ResponseStreamEvent::OutputItemAdded(OutputItemEvent { item: OutputItem::FunctionCall(call), .. })
+                if call.name == "lookup"
+        ));
+        assert!(
+            matches!(&received[2], ResponseStreamEvent::FunctionCallArgumentsDelta(e) if e.delta == "{}")
+        );
+        assert!(
+            matches!(&received[3], ResponseStreamEvent::ReasoningSummaryTextDelta(e) if e.delta == "Hm")
+        );
+        assert!(matches!(&received[4], ResponseStreamEvent::Unknown(data) if data["foo"] == 1));
+        assert_eq!(
+            received[5].response().and_then(|r| r.status.as_deref()),
+            Some("completed")
+        );
+        assert_eq!(stream.last_sequence_number(), Some(7));
+        assert_eq!(stream.gaps(), &[Range { start: 3, end: 5 }]);
+        assert!(stream.is_done());
     }
 }
@@ -0,0 +1,250 @@
+use crate::v1::rate_limit::{duration_from_secs, RateLimitInfo};
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// Controls how `OpenAIClient` retries failed requests.
+///
+/// A request is retried when the server answers with one of `retry_statuses`
+/// or when the connection fails or times out, until `max_attempts` attempts
+/// have been made. Delays grow exponentially from `base_delay` up to
+/// `max_delay`, unless the server tells us how long to wait through the
+/// `retry-after-ms`, `retry-after` or `x-ratelimit-reset-*` headers.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one.
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    /// Randomize each delay between half and the full computed value.
+    pub jitter: bool,
+    pub retry_statuses: Vec<StatusCode>,
+    pub retry_on_timeout: bool,
+    pub retry_on_connect: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(8),
+            jitter: true,
+            retry_statuses: vec![
+                StatusCode::REQUEST_TIMEOUT,
+                StatusCode::CONFLICT,
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+            retry_on_timeout: true,
+            retry_on_connect: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A policy that makes a single attempt and never retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    pub fn with_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    pub fn with_retry_statuses(mut self, retry_statuses: Vec<StatusCode>) -> Self {
+        self.retry_statuses = retry_statuses;
+        self
+    }
+
+    pub fn with_retry_on_timeout(mut self, retry_on_timeout: bool) -> Self {
+        self.retry_on_timeout = retry_on_timeout;
+        self
+    }
+
+    pub fn with_retry_on_connect(mut self, retry_on_connect: bool) -> Self {
+        self.retry_on_connect = retry_on_connect;
+        self
+    }
+
+    pub fn should_retry_status(&self, status: StatusCode) -> bool {
+        self.retry_statuses.contains(&status)
+    }
+
+    pub fn should_retry_error(&self, err: &reqwest::Error) -> bool {
+        (self.retry_on_timeout && err.is_timeout()) || (self.retry_on_connect && err.is_connect())
+    }
+
+    /// Delay before the attempt following `attempt` (1-based).
+    ///
+    /// Server-provided hints are honoured up to `max_delay`.
+    pub fn delay(&self, attempt: u32, headers: Option<&HeaderMap>) -> Duration {
+        if let Some(hint) = headers.and_then(retry_hint) {
+            return hint.min(self.max_delay);
+        }
+
+        let exponent = attempt.saturating_sub(1).min(31);
+        let delay = self
+            .base_delay
+            .saturating_mul(1u32 << exponent)
+            .min(self.max_delay);
+
+        if self.jitter {
+            let factor = 0.5 + random_fraction() / 2.0;
+            delay.mul_f64(factor)
+        } else {
+            delay
+        }
+    }
+}
+
+/// How long the server asked us to wait, from the response headers.
+fn retry_hint(headers: &HeaderMap) -> Option<Duration> {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+
+    // Unusable values are ignored, falling back to the next hint or backoff.
+    let hint = header("retry-after-ms")
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .and_then(|millis| duration_from_secs(millis / 1000.0))
+        .or_else(|| {
+            header("retry-after")
+                .and_then(|v| v.trim().parse::<f64>().ok())
+                .and_then(duration_from_secs)
+        });
+    if hint.is_some() {
+        return hint;
+    }
+
+    // Only wait for the budgets that are actually exhausted, unless the
+    // server did not say which one is.
+    let rate_limit = RateLimitInfo::from_headers(headers);
+    [
+        (rate_limit.remaining_requests, rate_limit.reset_requests),
+        (rate_limit.remaining_tokens, rate_limit.reset_tokens),
+    ]
+    .into_iter()
+    .filter(|(remaining, _)| remaining.is_none_or(|remaining| remaining == 0))
+    .filter_map(|(_, reset)| reset)
+    .max()
+}
+
+fn random_fraction() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(0);
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[test]
+    fn test_exponential_delay_without_jitter() {
+        let policy = RetryPolicy::new()
+            .with_base_delay(Duration::from_millis(100))
+            .with_max_delay(Duration::from_millis(350))
+            .with_jitter(false);
+        assert_eq!(policy.delay(1, None), Duration::from_millis(100));
+        assert_eq!(policy.delay(2, None), Duration::from_millis(200));
+        assert_eq!(policy.delay(3, None), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn test_jittered_delay_stays_in_range() {
+        let policy = RetryPolicy::new().with_base_delay(Duration::from_millis(100));
+        for _ in 0..100 {
+            let delay = policy.delay(1, None);
+            assert!(delay >= Duration::from_millis(50) && delay <= Duration::from_millis(100));
+        }
+    }
+
+    #[test]
+    fn test_delay_honours_retry_after_headers() {
+        let policy = RetryPolicy::new();
+        let delay = policy.delay(1, Some(&headers(&[("retry-after-ms", "1500")])));
+        assert_eq!(delay, Duration::from_millis(1500));
+        let delay = policy.delay(1, Some(&headers(&[("retry-after", "2")])));
+        assert_eq!(delay, Duration::from_secs(2));
+        let delay = policy.delay(1, Some(&headers(&[("retry-after", "120")])));
+        assert_eq!(delay, policy.max_delay);
+    }
+
+    #[test]
+    fn test_delay_ignores_unusable_retry_after() {
+        let policy = RetryPolicy::new().with_jitter(false);
+        for value in ["inf", "NaN", "-1", "1e30"] {
+            let delay = policy.delay(1, Some(&headers(&[("retry-after", value)])));
+            assert_eq!(delay, policy.base_delay, "retry-after: {value}");
+            let delay = policy.delay(1, Some(&headers(&[("retry-after-ms", value)])));
+            assert_eq!(delay, policy.base_delay, "retry-after-ms: {value}");
+        }
+        let delay = policy.delay(1, Some(&headers(&[("retry-after", "1e9")])));
+        assert_eq!(delay, policy.max_delay);
+        let delay = policy.delay(
+            1,
+            Some(&headers(&[("retry-after-ms", "inf"), ("retry-after", "2")])),
+        );
+        assert_eq!(delay, Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_delay_honours_exhausted_rate_limit_reset() {
+        let policy = RetryPolicy::new();
+        let delay = policy.delay(
+            1,
+            Some(&headers(&[
+                ("x-ratelimit-remaining-requests", "10"),
+                ("x-ratelimit-reset-requests", "6s"),
+                ("x-ratelimit-remaining-tokens", "0"),
+                ("x-ratelimit-reset-tokens", "1s"),
+            ])),
+        );
+        assert_eq!(delay, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_should_retry_status() {
+        let policy = RetryPolicy::new();
+        assert!(policy.should_retry_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(policy.should_retry_status(StatusCode::BAD_GATEWAY));
+        assert!(!policy.should_retry_status(StatusCode::BAD_REQUEST));
+        assert!(!policy.should_retry_status(StatusCode::UNAUTHORIZED));
+    }
+}
@@ -5,6 +5,7 @@ use std::collections::HashMap;
 
 use super::types;
 use crate::impl_builder_methods;
+use crate::v1::pagination::{CursorPage, PageItem};
 
 #[derive(Debug, Serialize, Clone)]
 pub struct CreateRunRequest {
@@ -100,13 +101,12 @@ pub struct RunObject {
     pub metadata: HashMap<String, String>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-pub struct ListRun {
-    pub object: String,
-    pub data: Vec<RunObject>,
-    pub first_id: String,
-    pub last_id: String,
-    pub has_more: bool,
+pub type ListRun = CursorPage<RunObject>;
+
+impl PageItem for RunObject {
+    fn page_id(&self) -> &str {
+        &self.id
+    }
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -151,11 +151,10 @@ pub struct RunStepObject {
     pub metadata: HashMap<String, String>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
-pub struct ListRunStep {
-    pub object: String,
-    pub data: Vec<RunStepObject>,
-    pub first_id: String,
-    pub last_id: String,
-    pub has_more: bool,
+pub type ListRunStep = CursorPage<RunStepObject>;
+
+impl PageItem for RunStepObject {
+    fn page_id(&self) -> &str {
+        &self.id
+    }
 }
@@ -0,0 +1,544 @@
+//! JSON Schemas for tool parameters and structured outputs, derived from
+//! Rust types.
+//!
+//! Implement `StrictSchema` by hand, or enable the `schemars` feature and
+//! wrap a type deriving `schemars::JsonSchema` in `SchemarsSchema`. Schemas
+//! are rewritten by `to_strict` into the subset accepted by strict mode.
+
+use crate::v1::chat_completion::chat_completion::ChatCompletionResponse;
+use crate::v1::chat_completion::{FinishReason, JsonSchemaFormat, ResponseFormat};
+use crate::v1::error::ParseError;
+use crate::v1::responses::responses::{OutputContent, OutputItem, ResponseObject, TextFormat};
+use crate::v1::types::{Function, FunctionParameters};
+use serde::de::DeserializeOwned;
+#[cfg(feature = "schemars")]
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// Types whose JSON form is described by a JSON Schema.
+pub trait StrictSchema {
+    /// Name of the schema, used for `response_format` and as the default
+    /// function name.
+    fn schema_name() -> String;
+
+    /// The schema as written; it does not need to be strict-mode compatible.
+    fn json_schema() -> Value;
+
+    /// The schema rewritten with `to_strict`.
+    fn strict_schema() -> Value {
+        to_strict(Self::json_schema())
+    }
+}
+
+/// Adapts a type deriving `schemars::JsonSchema` to `StrictSchema`.
+///
+/// It deserializes like `T`, so it can also be the target of
+/// `chat_completion_parsed` and `create_response_parsed`.
+#[cfg(feature = "schemars")]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(transparent)]
+pub struct SchemarsSchema<T>(pub T);
+
+#[cfg(feature = "schemars")]
+impl<T> SchemarsSchema<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl<T: schemars::JsonSchema> StrictSchema for SchemarsSchema<T> {
+    fn schema_name() -> String {
+        T::schema_name().into_owned()
+    }
+
+    fn json_schema() -> Value {
+        schemars::schema_for!(T).to_value()
+    }
+}
+
+/// String formats supported in strict mode; other formats are dropped.
+const STRICT_FORMATS: [&str; 9] = [
+    "date-time",
+    "time",
+    "date",
+    "duration",
+    "email",
+    "hostname",
+    "ipv4",
+    "ipv6",
+    "uuid",
+];
+
+/// Rewrites a schema into the form strict mode requires: every object
+/// lists all of its properties as required and disallows additional ones,
+/// with formerly optional properties made nullable; `oneOf` and type
+/// arrays become `anyOf`, and unsupported formats and `$schema` are removed.
+pub fn to_strict(mut schema: Value) -> Value {
+    make_strict(&mut schema);
+    schema
+}
+
+fn make_strict(schema: &mut Value) {
+    let Value::Object(object) = schema else {
+        return;
+    };
+    object.remove("$schema");
+    if let Some(one_of) = object.remove("oneOf") {
+        match object.remove("anyOf") {
+            // Both lists must hold, so neither may replace the other.
+            Some(any_of) => {
+                let all_of = object
+                    .entry("allOf")
+                    .or_insert_with(|| Value::Array(vec![]));
+                if let Value::Array(all_of) = all_of {
+                    all_of.push(json!({ "anyOf": any_of }));
+                    all_of.push(json!({ "anyOf": one_of }));
+                }
+            }
+            None => {
+                object.insert("anyOf".to_string(), one_of);
+            }
+        }
+    }
+    if let Some(Value::Array(types)) = object.get("type").cloned() {
+        object.remove("type");
+        let variants = types
+            .into_iter()
+            .map(|schema_type| {
+                if schema_type == "null" {
+                    json!({ "type": "null" })
+                } else {
+                    let mut variant = object.clone();
+                    variant.remove("description");
+                    if let Some(Value::Array(values)) = variant.get_mut("enum") {
+                        values.retain(|value| !value.is_null());
+                    }
+                    variant.insert("type".to_string(), schema_type);
+                    Value::Object(variant)
+                }
+            })
+            .collect();
+        object.retain(|key, _| key == "description" || key == "title");
+        object.insert("anyOf".to_string(), Value::Array(variants));
+    }
+    if let Some(Value::String(format)) = object.get("format") {
+        if !STRICT_FORMATS.contains(&format.as_str()) {
+            object.remove("format");
+        }
+    }
+
+    let required: Vec<String> = match object.get("required") {
+        Some(Value::Array(required)) => required
+            .iter()
+            .filter_map(|name| name.as_str().map(str::to_string))
+            .collect(),
+        _ => vec![],
+    };
+    if let Some(Value::Object(properties)) = object.get_mut("properties") {
+        for (name, property) in properties.iter_mut() {
+            if !required.contains(name) && !is_nullable(property) {
+                let inner = property.take();
+                *property = json!({ "anyOf": [inner, { "type": "null" }] });
+            }
+        }
+        let names: Vec<Value> = properties.keys().cloned().map(Value::String).collect();
+        object.insert("required".to_string(), Value::Array(names));
+        object.insert("additionalProperties".to_string(), Value::Bool(false));
+    }
+
+    for key in ["properties", "$defs", "definitions"] {
+        if let Some(Value::Object(children)) = object.get_mut(key) {
+            children.values_mut().for_each(make_strict);
+        }
+    }
+    for key in ["anyOf", "allOf", "prefixItems"] {
+        if let Some(Value::Array(children)) = object.get_mut(key) {
+            children.iter_mut().for_each(make_strict);
+        }
+    }
+    if let Some(items) = object.get_mut("items") {
+        make_strict(items);
+    }
+}
+
+fn is_nullable(schema: &Value) -> bool {
+    match schema.get("type") {
+        Some(Value::String(schema_type)) => schema_type == "null",
+        Some(Value::Array(types)) => types.iter().any(|t| t == "null"),
+        _ => matches!(
+            schema.get("anyOf").or_else(|| schema.get("oneOf")),
+            Some(Value::Array(variants)) if variants.iter().any(is_nullable)
+        ),
+    }
+}
+
+impl FunctionParameters {
+    /// Strict parameters for the arguments type `T`. The schema is kept as
+    /// is in `raw` rather than converted to `JSONSchemaDefine`.
+    pub fn from_schema<T: StrictSchema>() -> Result<Self, serde_json::Error> {
+        match T::strict_schema() {
+            Value::Object(schema) => FunctionParameters::from_raw(schema),
+            _ => Err(serde::de::Error::custom(
+                "parameters schema must be a JSON object",
+            )),
+        }
+    }
+}
+
+impl Function {
+    /// A strict function whose arguments are described by `T`.
+    pub fn from_schema<T: StrictSchema>(
+        name: impl Into<String>,
+        description: Option<String>,
+    ) -> Result<Self, serde_json::Error> {
+        Ok(Function {
+            name: name.into(),
+            description,
+            parameters: FunctionParameters::from_schema::<T>()?,
+            strict: Some(true),
+        })
+    }
+}
+
+impl ResponseFormat {
+    /// A Chat Completions response format asking for JSON matching `T`.
+    pub fn from_schema<T: StrictSchema>() -> Self {
+        ResponseFormat::JsonSchema {
+            json_schema: JsonSchemaFormat {
+                name: sanitize_name(&T::schema_name()),
+                description: None,
+                schema: Some(T::strict_schema()),
+                strict: Some(true),
+            },
+        }
+    }
+}
+
+impl TextFormat {
+    /// A Responses API text format asking for JSON matching `T`.
+    pub fn from_schema<T: StrictSchema>() -> Self {
+        TextFormat::JsonSchema {
+            name: sanitize_name(&T::schema_name()),
+            schema: T::strict_schema(),
+            description: None,
+            strict: Some(true),
+        }
+    }
+}
+
+/// A structured output parsed into `T`, with the response it came from.
+#[derive(Debug)]
+pub struct Parsed<T, R> {
+    pub parsed: T,
+    pub response: R,
+}
+
+impl<T> Parsed<T, ChatCompletionResponse> {
+    /// Parses the first choice of a chat completion.
+    pub fn from_chat_completion(response: ChatCompletionResponse) -> Result<Self, ParseError>
+    where
+        T: DeserializeOwned,
+    {
+        let choice = response.choices.first().ok_or(ParseError::MissingContent)?;
+        if let Some(refusal) = &choice.message.refusal {
+            return Err(ParseError::Refusal(refusal.clone()));
+        }
+        match &choice.finish_reason {
+            Some(FinishReason::length) => {
+                return Err(ParseError::Incomplete {
+                    reason: "length".to_string(),
+                })
+            }
+            Some(FinishReason::content_filter) => {
+                return Err(ParseError::Incomplete {
+                    reason: "content_filter".to_string(),
+                })
+            }
+            _ => {}
+        }
+        let content = choice
+            .message
+            .content
+            .as_deref()
+            .ok_or(ParseError::MissingContent)?;
+        Ok(Parsed {
+            parsed: parse_json(content)?,
+            response,
+        })
+    }
+}
+
+impl<T> Parsed<T, ResponseObject> {
+    /// Parses the output text of a response.
+    pub fn from_response(response: ResponseObject) -> Result<Self, ParseError>
+    where
+        T: DeserializeOwned,
+    {
+        let refusal = response
+            .output
+            .iter()
+            .filter_map(|item| match item {
+                OutputItem::Message(message) => Some(message),
+                _ => None,
+            })
+            .flat_map(|message| &message.content)
+            .find_map(|content| match content {
+                OutputContent::Refusal { refusal } => Some(refusal),
+                _ => None,
+            });
+        if let Some(refusal) = refusal {
+            return Err(ParseError::Refusal(refusal.clone()));
+        }
+        if response.status.as_deref() == Some("incomplete") {
+            let reason = response
+                .incomplete_details
+                .as_ref()
+                .and_then(|details| details.get("reason"))
+                .and_then(Value::as_str)
+                .unwrap_or("incomplete");
+            return Err(ParseError::Incomplete {
+                reason: reason.to_string(),
+            });
+        }
+        let text = response.output_text();
+        if text.is_empty() {
+            return Err(ParseError::MissingContent);
+        }
+        Ok(Parsed {
+            parsed: parse_json(&text)?,
+            response,
+        })
+    }
+}
+
+fn parse_json<T: DeserializeOwned>(content: &str) -> Result<T, ParseError> {
+    serde_json::from_str(content).map_err(|source| ParseError::Json {
+        source,
+        content: content.to_string(),
+    })
+}
+
+/// Schema names may only contain letters, digits, `_` and `-`.
+pub(crate) fn sanitize_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(64)
+        .collect();
+    if name.is_empty() {
+        "response".to_string()
+    } else {
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Weather;
+
+    impl StrictSchema for Weather {
+        fn schema_name() -> String {
+            "Weather<Report>".to_string()
+        }
+
+        fn json_schema() -> Value {
+            json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "type": "object",
+                "properties": {
+                    "city": { "type": "string" },
+                    "days": { "type": "integer", "format": "uint8", "minimum": 0 },
+                    "unit": { "type": ["string", "null"], "enum": ["c", "f", null] },
+                    "alerts": { "type": "array", "items": { "$ref": "#/$defs/Alert" } }
+                },
+                "required": ["city", "alerts"],
+                "$defs": {
+                    "Alert": {
+                        "oneOf": [
+                            { "type": "object", "properties": { "level": { "const": "high" } } },
+                            { "type": "string", "format": "date-time" }
+                        ]
+                    }
+                }
+            })
+        }
+    }
+
+    #[test]
+    fn test_to_strict() {
+        let schema = Weather::strict_schema();
+        assert_eq!(schema.get("$schema"), None);
+        assert_eq!(schema["additionalProperties"], false);
+        assert_eq!(
+            schema["required"].as_array().unwrap().len(),
+            4,
+            "every property is required"
+        );
+        assert_eq!(
+            schema["properties"]["days"],
+            json!({ "anyOf": [{ "type": "integer", "minimum": 0 }, { "type": "null" }] })
+        );
+        assert_eq!(
+            schema["properties"]["unit"]["anyOf"][1],
+            json!({ "type": "null" })
+        );
+        let alert = &schema["$defs"]["Alert"]["anyOf"];
+        assert_eq!(alert[0]["additionalProperties"], false);
+        assert_eq!(alert[0]["required"], json!(["level"]));
+        assert_eq!(alert[1]["format"], "date-time");
+    }
+
+    #[test]
+    fn test_to_strict_keeps_any_of_next_to_one_of() {
+        let schema = to_strict(json!({
+            "anyOf": [{ "type": "string" }, { "type": "integer" }],
+            "oneOf": [{ "minLength": 1 }, { "minimum": 0 }]
+        }));
+        assert_eq!(schema.get("anyOf"), None);
+        assert_eq!(
+            schema["allOf"],
+            json!([
+                { "anyOf": [{ "type": "string" }, { "type": "integer" }] },
+                { "anyOf": [{ "minLength": 1 }, { "minimum": 0 }] }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_function_from_schema() {
+        let function = Function::from_schema::<Weather>("get_weather", None).unwrap();
+        let value = serde_json::to_value(&function).unwrap();
+        assert_eq!(value["strict"], true);
+        assert_eq!(value["parameters"]["additionalProperties"], false);
+        assert_eq!(
+            value["parameters"]["properties"]["alerts"]["items"]["$ref"],
+            "#/$defs/Alert"
+        );
+        assert!(value["parameters"]["$defs"]["Alert"]["anyOf"].is_array());
+
+        let format = serde_json::to_value(ResponseFormat::from_schema::<Weather>()).unwrap();
+        assert_eq!(format["type"], "json_schema");
+        assert_eq!(format["json_schema"]["name"], "Weather_Report_");
+        assert_eq!(format["json_schema"]["strict"], true);
+    }
+
+    struct Shapes;
+
+    impl StrictSchema for Shapes {
+        fn schema_name() -> String {
+            "Shapes".to_string()
+        }
+
+        fn json_schema() -> Value {
+            json!({
+                "type": "object",
+                "properties": {
+                    "point": {
+                        "type": "array",
+                        "prefixItems": [{ "type": "number" }, { "type": "number" }]
+                    },
+                    "sides": { "type": "integer", "enum": [3, 4, 6], "default": 4 },
+                    "style": {
+                        "allOf": [{ "$ref": "#/$defs/Style" }],
+                        "description": "Fill and stroke"
+                    }
+                },
+                "required": ["point", "sides", "style"],
+                "$defs": {
+                    "Style": { "type": "object", "properties": { "fill": { "type": "string" } } }
+                }
+            })
+        }
+    }
+
+    #[test]
+    fn test_function_parameters_keep_schema() {
+        let parameters = FunctionParameters::from_schema::<Shapes>().unwrap();
+        assert_eq!(
+            serde_json::to_value(&parameters).unwrap(),
+            Shapes::strict_schema()
+        );
+        let value = serde_json::to_value(&parameters).unwrap();
+        assert_eq!(value["properties"]["sides"]["enum"], json!([3, 4, 6]));
+        assert_eq!(value["properties"]["sides"]["default"], 4);
+        assert_eq!(
+            value["properties"]["point"]["prefixItems"][1],
+            json!({ "type": "number" })
+        );
+        assert_eq!(
+            value["properties"]["style"]["allOf"][0]["$ref"],
+            "#/$defs/Style"
+        );
+        assert_eq!(value["$defs"]["Style"]["additionalProperties"], false);
+
+        let parsed: FunctionParameters = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(parsed, parameters);
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), value);
+    }
+
+    #[test]
+    fn test_typed_parameters_round_trip() {
+        let value = json!({
+            "type": "object",
+            "properties": { "city": { "type": "string", "enum": ["Oslo", "Lima"] } },
+            "required": ["city"],
+            "additionalProperties": false
+        });
+        let parsed: FunctionParameters = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(parsed.raw, None);
+        assert_eq!(parsed.required, Some(vec!["city".to_string()]));
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), value);
+    }
+
+    #[test]
+    fn test_enum_serializes_as_enum() {
+        let define = crate::v1::types::JSONSchemaDefine {
+            schema_type: Some(crate::v1::types::JSONSchemaType::String),
+            enum_values: Some(vec!["a".to_string()]),
+            ..Default::default()
+        }
+        .nullable();
+        assert_eq!(
+            serde_json::to_value(define).unwrap(),
+            json!({ "anyOf": [{ "type": "string", "enum": ["a"] }, { "type": "null" }] })
+        );
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_schemars_derive() {
+        #[allow(dead_code)]
+        #[derive(serde::Deserialize, schemars::JsonSchema)]
+        struct Forecast {
+            city: String,
+            high: Option<f64>,
+            kind: Kind,
+        }
+
+        #[allow(dead_code)]
+        #[derive(serde::Deserialize, schemars::JsonSchema)]
+        enum Kind {
+            Sunny,
+            Rain { mm: u32 },
+        }
+
+        let schema = SchemarsSchema::<Forecast>::strict_schema();
+        assert_eq!(schema["additionalProperties"], false);
+        assert_eq!(schema["required"], json!(["city", "high", "kind"]));
+        assert!(schema.to_string().find("oneOf").is_none());
+        assert!(FunctionParameters::from_schema::<SchemarsSchema<Forecast>>().is_ok());
+
+        let parsed: SchemarsSchema<Forecast> =
+            serde_json::from_str(r#"{"city":"Oslo","high":null,"kind":"Sunny"}"#).unwrap();
+        assert_eq!(parsed.into_inner().city, "Oslo");
+    }
+}
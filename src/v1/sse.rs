@@ -0,0 +1,391 @@
+//! Incremental decoder for `text/event-stream` responses, following the
+//! WHATWG server-sent events specification.
+
+use crate::v1::error::APIError;
+use futures_util::Stream;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// Default limit on the size of a single line or event, in bytes.
+pub const DEFAULT_MAX_EVENT_SIZE: usize = 16 * 1024 * 1024;
+
+/// One dispatched server-sent event.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SseEvent {
+    /// The `event:` field; `None` for the default `message` type.
+    pub event: Option<String>,
+    /// The `data:` lines joined with `\n`.
+    pub data: String,
+    /// The last event ID seen on the stream.
+    pub id: Option<String>,
+    /// Reconnection time requested with a `retry:` field.
+    pub retry: Option<Duration>,
+}
+
+/// Splits a byte stream into server-sent events.
+///
+/// Bytes are buffered until a full line is available, so multi-byte UTF-8
+/// characters split across chunks are decoded correctly. Lines may end in
+/// `\n`, `\r\n` or `\r`. Comment lines and unknown fields are ignored, and an
+/// event still incomplete when the stream ends is discarded.
+#[derive(Debug)]
+pub struct SseDecoder {
+    buffer: Vec<u8>,
+    /// Start of the unprocessed part of `buffer`.
+    position: usize,
+    started: bool,
+    event: Option<String>,
+    data: String,
+    has_data: bool,
+    last_event_id: Option<String>,
+    retry: Option<Duration>,
+    max_event_size: usize,
+}
+
+impl Default for SseDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SseDecoder {
+    pub fn new() -> Self {
+        Self::with_max_event_size(DEFAULT_MAX_EVENT_SIZE)
+    }
+
+    /// A decoder that fails once a line or event grows beyond
+    /// `max_event_size` bytes instead of buffering without bound.
+    pub fn with_max_event_size(max_event_size: usize) -> Self {
+        Self {
+            buffer: Vec::new(),
+            position: 0,
+            started: false,
+            event: None,
+            data: String::new(),
+            has_data: false,
+            last_event_id: None,
+            retry: None,
+            max_event_size,
+        }
+    }
+
+    pub fn feed(&mut self, chunk: &[u8]) {
+        if self.position > 0 && self.position >= self.buffer.len() / 2 {
+            self.buffer.drain(..self.position);
+            self.position = 0;
+        }
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    /// The next complete event in the bytes fed so far.
+    pub fn next_event(&mut self) -> Result<Option<SseEvent>, APIError> {
+        while let Some((line_end, next_line)) = self.find_line_end() {
+            let line = &self.buffer[self.position..line_end];
+            let line = if !self.started {
+                self.started = true;
+                line.strip_prefix("\u{feff}".as_bytes()).unwrap_or(line)
+            } else {
+                line
+            };
+            let line = String::from_utf8_lossy(line).into_owned();
+            self.position = next_line;
+
+            if let Some(event) = self.process_line(&line)? {
+                return Ok(Some(event));
+            }
+        }
+
+        if self.buffer.len() - self.position > self.max_event_size {
+            return Err(Self::too_large(self.max_event_size));
+        }
+        Ok(None)
+    }
+
+    /// End of the current line and start of the next, if a full line is
+    /// buffered. A trailing `\r` waits for more data in case `\n` follows.
+    fn find_line_end(&self) -> Option<(usize, usize)> {
+        let rest = &self.buffer[self.position..];
+        let offset = rest.iter().position(|&b| b == b'\n' || b == b'\r')?;
+        let end = self.position + offset;
+        match (rest[offset], rest.get(offset + 1)) {
+            (b'\n', _) => Some((end, end + 1)),
+            (_, Some(b'\n')) => Some((end, end + 2)),
+            (_, Some(_)) => Some((end, end + 1)),
+            (_, None) => None,
+        }
+    }
+
+    fn process_line(&mut self, line: &str) -> Result<Option<SseEvent>, APIError> {
+        if line.is_empty() {
+            return Ok(self.dispatch());
+        }
+        if line.starts_with(':') {
+            return Ok(None);
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "event" => self.event = Some(value.to_string()),
+            "data" => {
+                if self.has_data {
+                    self.data.push('\n');
+                }
+                self.data.push_str(value);
+                self.has_data = true;
+                if self.data.len() > self.max_event_size {
+                    return Err(Self::too_large(self.max_event_size));
+                }
+            }
+            "id" if !value.contains('\0') => self.last_event_id = Some(value.to_string()),
+            "retry" if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => {
+                if let Ok(ms) = value.parse() {
+                    self.retry = Some(Duration::from_millis(ms));
+                }
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        let event = self.event.take();
+        if !std::mem::take(&mut self.has_data) {
+            return None;
+        }
+        Some(SseEvent {
+            event: event.filter(|event| !event.is_empty()),
+            data: std::mem::take(&mut self.data),
+            id: self.last_event_id.clone().filter(|id| !id.is_empty()),
+            retry: self.retry,
+        })
+    }
+
+    fn too_large(max_event_size: usize) -> APIError {
+        APIError::StreamError {
+            message: format!("server-sent event exceeds {max_event_size} bytes"),
+        }
+    }
+}
+
+/// Adapts a stream of body chunks into a stream of server-sent events.
+pub struct SseStream<S> {
+    inner: S,
+    decoder: SseDecoder,
+    finished: bool,
+}
+
+impl<S> SseStream<S>
+where
+    S: Stream<Item = Result<bytes::Bytes, APIError>> + Unpin,
+{
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            decoder: SseDecoder::new(),
+            finished: false,
+        }
+    }
+}
+
+impl<S> Stream for SseStream<S>
+where
+    S: Stream<Item = Result<bytes::Bytes, APIError>> + Unpin,
+{
+    type Item = Result<SseEvent, APIError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if self.finished {
+                return Poll::Ready(None);
+            }
+            match self.decoder.next_event() {
+                Ok(Some(event)) => return Poll::Ready(Some(Ok(event))),
+                Ok(None) => {}
+                Err(error) => {
+                    self.finished = true;
+                    return Poll::Ready(Some(Err(error)));
+                }
+            }
+
+            match Pin::new(&mut self.inner).poll_next(cx) {
+                Poll::Ready(Some(Ok(chunk))) => self.decoder.feed(&chunk),
+                Poll::Ready(Some(Err(error))) => {
+                    self.finished = true;
+                    return Poll::Ready(Some(Err(error)));
+                }
+                Poll::Ready(None) => {
+                    self.finished = true;
+                    return Poll::Ready(None);
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_chunks(chunks: &[&[u8]]) -> Vec<SseEvent> {
+        let mut decoder = SseDecoder::new();
+        let mut events = vec![];
+        for chunk in chunks {
+            decoder.feed(chunk);
+            while let Some(event) = decoder.next_event().unwrap() {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    fn event(event: Option<&str>, data: &str, id: Option<&str>) -> SseEvent {
+        SseEvent {
+            event: event.map(str::to_string),
+            data: data.to_string(),
+            id: id.map(str::to_string),
+            retry: None,
+        }
+    }
+
+    #[test]
+    fn test_fields_and_comments() {
+        let input = b"\xEF\xBB\xBF: keep-alive\n\
+            event: response.created\n\
+            id: 1\n\
+            data: {\"a\":1}\n\n\
+            data\n\
+            data:  two spaces\n\
+            unknown: x\n\n\
+            event: ignored\n\n\
+            retry: 1500\n\
+            data: last\n\n";
+        let events = decode_chunks(&[input]);
+        assert_eq!(
+            events[..2],
+            [
+                event(Some("response.created"), "{\"a\":1}", Some("1")),
+                event(None, "\n two spaces", Some("1")),
+            ]
+        );
+        assert_eq!(events[2].data, "last");
+        assert_eq!(events[2].retry, Some(Duration::from_millis(1500)));
+        assert_eq!(events.len(), 3);
+    }
+
+    #[test]
+    fn test_line_endings() {
+        let events = decode_chunks(&[b"data: a\r\n\r\ndata: b\r\rdata: c\n\n"]);
+        assert_eq!(
+            events,
+            [
+                event(None, "a", None),
+                event(None, "b", None),
+                event(None, "c", None)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_utf8_split_across_chunks() {
+        let input = "data: こんにちは 👋\n\n".as_bytes();
+        let events = decode_chunks(&[&input[..8], &input[8..13], &input[13..]]);
+        assert_eq!(events, [event(None, "こんにちは 👋", None)]);
+    }
+
+    #[test]
+    fn test_incomplete_event_is_discarded() {
+        assert!(decode_chunks(&[b"data: partial\n"]).is_empty());
+    }
+
+    #[test]
+    fn test_bounded_buffer() {
+        let mut decoder = SseDecoder::with_max_event_size(16);
+        decoder.feed(&[b'a'; 32]);
+        assert!(decoder.next_event().is_err());
+
+        let mut decoder = SseDecoder::with_max_event_size(16);
+        decoder.feed(b"data: 0123456789\ndata: 0123456789\n");
+        assert!(decoder.next_event().is_err());
+    }
+
+    /// xorshift64, so the fuzz cases are reproducible.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next() % n as u64) as usize
+        }
+    }
+
+    #[test]
+    fn fuzz_chunking_does_not_change_events() {
+        let pieces: [&[u8]; 12] = [
+            b"data: ",
+            b"event: delta\n",
+            "données ✓\n".as_bytes(),
+            b"\n",
+            b"\r\n",
+            b"\r",
+            b": comment\n",
+            b"id: 7\n",
+            b"retry: 20\n",
+            b"data:[DONE]\n",
+            "data: 🦀\n".as_bytes(),
+            b"bogus\n",
+        ];
+        let mut rng = Rng(0x5eed_1234_abcd_0001);
+
+        for _ in 0..500 {
+            let mut input = Vec::new();
+            for _ in 0..rng.below(40) {
+                input.extend_from_slice(pieces[rng.below(pieces.len())]);
+            }
+            // A trailing blank line, so a final `\r` is a complete line ending.
+            input.extend_from_slice(b"\n\n");
+
+            let expected = decode_chunks(&[&input]);
+            let mut chunks = vec![];
+            let mut rest = &input[..];
+            while !rest.is_empty() {
+                let (chunk, tail) = rest.split_at(1 + rng.below(rest.len().min(9)));
+                chunks.push(chunk);
+                rest = tail;
+            }
+            assert_eq!(decode_chunks(&chunks), expected, "input: {input:?}");
+        }
+    }
+
+    #[test]
+    fn fuzz_arbitrary_bytes_do_not_panic() {
+        let mut rng = Rng(0xdead_beef_cafe_f00d);
+        for _ in 0..500 {
+            let input: Vec<u8> = (0..rng.below(256))
+                .map(|_| match rng.below(4) {
+                    0 => b'\n',
+                    1 => b'\r',
+                    2 => b':',
+                    _ => rng.next() as u8,
+                })
+                .collect();
+            let mut decoder = SseDecoder::with_max_event_size(64);
+            for chunk in input.chunks(1 + rng.below(16)) {
+                decoder.feed(chunk);
+                while let Ok(Some(event)) = decoder.next_event() {
+                    assert!(event.data.len() <= 64);
+                }
+            }
+        }
+    }
+}
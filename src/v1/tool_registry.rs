@@ -0,0 +1,260 @@
+//! Registry of Rust handlers for function tools, used by
+//! `OpenAIClient::run_tools` and `OpenAIClient::run_response_tools` to answer
+//! tool calls until the model produces a final answer.
+
+use crate::v1::chat_completion::chat_completion::ChatCompletionResponse;
+use crate::v1::chat_completion::{ChatCompletionMessage, Tool, ToolType};
+use crate::v1::responses::responses::{InputItem, ResponseObject, ResponseTool};
+use crate::v1::types;
+use futures_util::future::{self, BoxFuture};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+
+/// Default limit on model round trips in a tool loop.
+pub const DEFAULT_MAX_ITERATIONS: usize = 10;
+
+type Handler = dyn Fn(Value) -> BoxFuture<'static, Result<String, String>> + Send + Sync;
+
+#[derive(Clone)]
+struct RegisteredTool {
+    function: types::Function,
+    handler: Arc<Handler>,
+}
+
+/// Function tools and the async handlers that answer them.
+///
+/// ```no_run
+/// # use openai_api_rs::v1::tool_registry::ToolRegistry;
+/// # use openai_api_rs::v1::types;
+/// # fn schema() -> types::Function {
+/// #     types::Function {
+/// #         name: "get_coin_price".to_string(),
+/// #         description: Some("Get the price of a cryptocurrency".to_string()),
+/// #         parameters: types::FunctionParameters::default(),
+/// #         strict: None,
+/// #     }
+/// # }
+/// #[derive(serde::Deserialize)]
+/// struct Args {
+///     coin: String,
+/// }
+///
+/// let mut registry = ToolRegistry::new();
+/// registry.register(schema(), |args: Args| async move {
+///     Ok::<_, std::io::Error>(format!("{} costs 1000 USD", args.coin))
+/// });
+/// ```
+#[derive(Clone)]
+pub struct ToolRegistry {
+    tools: BTreeMap<String, RegisteredTool>,
+    pub max_iterations: usize,
+}
+
+impl fmt::Debug for ToolRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ToolRegistry")
+            .field("tools", &self.tools.keys().collect::<Vec<_>>())
+            .field("max_iterations", &self.max_iterations)
+            .finish()
+    }
+}
+
+impl Default for ToolRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ToolRegistry {
+    pub fn new() -> Self {
+        Self {
+            tools: BTreeMap::new(),
+            max_iterations: DEFAULT_MAX_ITERATIONS,
+        }
+    }
+
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Registers `handler` under `function.name`, replacing any earlier
+    /// handler with that name.
+    ///
+    /// The arguments are deserialized into `A`; use `serde_json::Value` to
+    /// take them as they are. A string result is sent to the model as is,
+    /// anything else as JSON. Errors are reported to the model so it can
+    /// recover, rather than ending the loop.
+    pub fn register<A, R, E, F, Fut>(&mut self, function: types::Function, handler: F) -> &mut Self
+    where
+        A: DeserializeOwned,
+        R: Serialize,
+        E: fmt::Display,
+        F: Fn(A) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<R, E>> + Send + 'static,
+    {
+        let handler = move |arguments: Value| -> BoxFuture<'static, Result<String, String>> {
+            let args = match serde_json::from_value::<A>(arguments) {
+                Ok(args) => args,
+                Err(error) => {
+                    return Box::pin(future::ready(Err(format!("invalid arguments: {error}"))))
+                }
+            };
+            let call = handler(args);
+            Box::pin(async move {
+                let output = call.await.map_err(|error| error.to_string())?;
+                match serde_json::to_value(output) {
+                    Ok(Value::String(text)) => Ok(text),
+                    Ok(value) => Ok(value.to_string()),
+                    Err(error) => Err(format!("invalid tool output: {error}")),
+                }
+            })
+        };
+        self.tools.insert(
+            function.name.clone(),
+            RegisteredTool {
+                function,
+                handler: Arc::new(handler),
+            },
+        );
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tools.is_empty()
+    }
+
+    /// The registered functions as Chat Completions tools.
+    pub fn tools(&self) -> Vec<Tool> {
+        self.tools
+            .values()
+            .map(|tool| Tool {
+                r#type: ToolType::Function,
+                function: tool.function.clone(),
+            })
+            .collect()
+    }
+
+    /// The registered functions as Responses API function tools.
+    pub fn response_tools(&self) -> Vec<ResponseTool> {
+        self.tools
+            .values()
+            .map(|tool| ResponseTool::Function(tool.function.clone()))
+            .collect()
+    }
+
+    /// Runs the handler for `name` with JSON-encoded `arguments`. Unknown
+    /// tools, bad arguments and handler errors come back as an error message
+    /// meant for the model.
+    pub async fn call(&self, name: &str, arguments: &str) -> String {
+        let Some(tool) = self.tools.get(name) else {
+            return format!("Error: unknown tool `{name}`");
+        };
+        let arguments = if arguments.trim().is_empty() {
+            Ok(Value::Object(Default::default()))
+        } else {
+            serde_json::from_str(arguments)
+        };
+        let result = match arguments {
+            Ok(arguments) => (tool.handler)(arguments).await,
+            Err(error) => Err(format!("invalid arguments: {error}")),
+        };
+        result.unwrap_or_else(|error| format!("Error: {error}"))
+    }
+
+    /// Runs several calls concurrently, returning outputs in the same order.
+    pub async fn call_all<'a>(
+        &self,
+        calls: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Vec<String> {
+        future::join_all(
+            calls
+                .into_iter()
+                .map(|(name, arguments)| self.call(name, arguments)),
+        )
+        .await
+    }
+}
+
+/// Result of `OpenAIClient::run_tools`.
+#[derive(Debug)]
+pub struct ToolRun {
+    /// The request messages followed by every assistant and tool message
+    /// exchanged in the loop.
+    pub messages: Vec<ChatCompletionMessage>,
+    /// The final response, which asks for no more tool calls.
+    pub response: ChatCompletionResponse,
+    /// Number of chat completion requests made.
+    pub iterations: usize,
+}
+
+/// Result of `OpenAIClient::run_response_tools`.
+#[derive(Debug)]
+pub struct ResponseToolRun {
+    /// The request input followed by every output item and tool output.
+    pub items: Vec<InputItem>,
+    /// The final response, which asks for no more function calls.
+    pub response: ResponseObject,
+    /// Number of responses created.
+    pub iterations: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn function(name: &str) -> types::Function {
+        types::Function {
+            name: name.to_string(),
+            description: None,
+            parameters: types::FunctionParameters {
+                schema_type: types::JSONSchemaType::Object,
+                properties: None,
+                required: None,
+                ..Default::default()
+            },
+            strict: None,
+        }
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Add {
+        a: i64,
+        b: i64,
+    }
+
+    #[tokio::test]
+    async fn test_call_reports_errors_to_the_model() {
+        let mut registry = ToolRegistry::new();
+        registry
+            .register(function("add"), |args: Add| async move {
+                Ok::<_, String>(serde_json::json!({"sum": args.a + args.b}))
+            })
+            .register(function("fail"), |_: Value| async move {
+                Err::<String, _>("database is down")
+            });
+
+        let outputs = registry
+            .call_all([
+                ("add", r#"{"a":1,"b":2}"#),
+                ("add", r#"{"a":1}"#),
+                ("fail", ""),
+                ("missing", "{}"),
+            ])
+            .await;
+
+        assert_eq!(outputs[0], r#"{"sum":3}"#);
+        assert!(outputs[1].starts_with("Error: invalid arguments: missing field `b`"));
+        assert_eq!(outputs[2], "Error: database is down");
+        assert_eq!(outputs[3], "Error: unknown tool `missing`");
+        assert_eq!(registry.tools().len(), 2);
+        let response_tools = serde_json::to_value(registry.response_tools()).unwrap();
+        assert_eq!(response_tools[0]["type"], "function");
+        assert_eq!(response_tools[0]["name"], "add");
+    }
+}
@@ -1,4 +1,5 @@
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Number, Value};
 use std::collections::HashMap;
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
@@ -7,16 +8,106 @@ pub struct Function {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     pub parameters: FunctionParameters,
+    /// Makes the model follow `parameters` exactly. The schema must then
+    /// list every property as required and disallow additional properties.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict: Option<bool>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FunctionParameters {
-    #[serde(rename = "type")]
     pub schema_type: JSONSchemaType,
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub properties: Option<HashMap<String, Box<JSONSchemaDefine>>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub required: Option<Vec<String>>,
+    pub additional_properties: Option<bool>,
+    /// Definitions referenced from `$ref`s in the properties.
+    pub defs: Option<HashMap<String, Box<JSONSchemaDefine>>>,
+    /// A complete schema, sent as it is instead of the fields above. Set by
+    /// `from_schema`, and when deserializing a schema those fields cannot
+    /// represent exactly.
+    pub raw: Option<Map<String, Value>>,
+}
+
+impl Default for FunctionParameters {
+    fn default() -> Self {
+        Self {
+            schema_type: JSONSchemaType::Object,
+            properties: None,
+            required: None,
+            additional_properties: None,
+            defs: None,
+            raw: None,
+        }
+    }
+}
+
+/// The wire form of the typed `FunctionParameters` fields.
+#[derive(Deserialize, Serialize)]
+struct TypedParameters {
+    #[serde(rename = "type")]
+    schema_type: JSONSchemaType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    properties: Option<HashMap<String, Box<JSONSchemaDefine>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    required: Option<Vec<String>>,
+    #[serde(
+        rename = "additionalProperties",
+        skip_serializing_if = "Option::is_none"
+    )]
+    additional_properties: Option<bool>,
+    #[serde(rename = "$defs", skip_serializing_if = "Option::is_none")]
+    defs: Option<HashMap<String, Box<JSONSchemaDefine>>>,
+}
+
+impl Serialize for FunctionParameters {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if let Some(raw) = &self.raw {
+            return raw.serialize(serializer);
+        }
+        TypedParameters {
+            schema_type: self.schema_type.clone(),
+            properties: self.properties.clone(),
+            required: self.required.clone(),
+            additional_properties: self.additional_properties,
+            defs: self.defs.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for FunctionParameters {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let schema = Value::Object(Map::deserialize(deserializer)?);
+        let typed = serde_json::from_value::<TypedParameters>(schema.clone())
+            .ok()
+            .filter(|typed| serde_json::to_value(typed).ok().as_ref() == Some(&schema));
+        if let Some(typed) = typed {
+            return Ok(FunctionParameters {
+                schema_type: typed.schema_type,
+                properties: typed.properties,
+                required: typed.required,
+                additional_properties: typed.additional_properties,
+                defs: typed.defs,
+                raw: None,
+            });
+        }
+        let Value::Object(raw) = schema else {
+            unreachable!("deserialized from a map")
+        };
+        FunctionParameters::from_raw(raw).map_err(de::Error::custom)
+    }
+}
+
+impl FunctionParameters {
+    /// Parameters sent as the given schema, which must have a valid `type`.
+    pub fn from_raw(raw: Map<String, Value>) -> Result<Self, serde_json::Error> {
+        let schema_type = raw.get("type").cloned().unwrap_or(Value::Null);
+        Ok(FunctionParameters {
+            schema_type: serde_json::from_value(schema_type)?,
+            raw: Some(raw),
+            ..Default::default()
+        })
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
@@ -24,6 +115,7 @@ pub struct FunctionParameters {
 pub enum JSONSchemaType {
     Object,
     Number,
+    Integer,
     String,
     Array,
     Null,
@@ -32,18 +124,69 @@ pub enum JSONSchemaType {
 
 #[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq)]
 pub struct JSONSchemaDefine {
-    #[serde(rename = "type")]
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
     pub schema_type: Option<JSONSchemaType>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "enum",
+        alias = "enum_values",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub enum_values: Option<Vec<String>>,
+    #[serde(rename = "const", skip_serializing_if = "Option::is_none")]
+    pub const_value: Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub properties: Option<HashMap<String, Box<JSONSchemaDefine>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub required: Option<Vec<String>>,
+    #[serde(
+        rename = "additionalProperties",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub additional_properties: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub items: Option<Box<JSONSchemaDefine>>,
+    #[serde(rename = "anyOf", skip_serializing_if = "Option::is_none")]
+    pub any_of: Option<Vec<JSONSchemaDefine>>,
+    #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    #[serde(rename = "$defs", skip_serializing_if = "Option::is_none")]
+    pub defs: Option<HashMap<String, Box<JSONSchemaDefine>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<Number>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<Number>,
+    #[serde(rename = "minLength", skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<u64>,
+    #[serde(rename = "maxLength", skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<u64>,
+    #[serde(rename = "minItems", skip_serializing_if = "Option::is_none")]
+    pub min_items: Option<u64>,
+    #[serde(rename = "maxItems", skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// `date-time`, `email`, `uuid` and similar string formats.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+}
+
+impl JSONSchemaDefine {
+    /// A schema that also accepts `null`, the strict-mode way to express an
+    /// optional property.
+    pub fn nullable(self) -> Self {
+        JSONSchemaDefine {
+            any_of: Some(vec![
+                self,
+                JSONSchemaDefine {
+                    schema_type: Some(JSONSchemaType::Null),
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]